@@ -1,7 +0,0 @@
-// Parametros
-pub const TAMANHO_VIZINHANCA: i32 = 1; // Definindo o tamanho da vizinhança (a distância máxima em cada direção)
-pub const ALPHA: f64 = 12.0;
-pub const K1: f64 = 0.5;
-pub const K2: f64 = 0.5;
-pub const NUM_FORMIGAS: i32 = 40;
-pub const NUM_INTERACOES: i64 = 50_000_000;
@@ -1,5 +0,0 @@
-pub mod cenario;
-pub mod constantes;
-pub mod formiga;
-pub mod grao;
-pub mod outros;
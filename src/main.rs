@@ -1,20 +1,219 @@
-mod lib;
-use lib::cenario::Cenario;
-use lib::constantes::{NUM_FORMIGAS, NUM_INTERACOES};
-use lib::formiga::{gerar_formigas, Formiga};
-use lib::grao::{gerar_graos, ler_graos_de_arquivo, ler_graos_de_arquivo_normalizado};
+mod simulacao;
+use simulacao::anttree::AntTree;
+use simulacao::automatico::estimar_parametros;
+use simulacao::avaliacao::{avaliar, Avaliacao};
+use simulacao::cenario::Cenario;
+use simulacao::classicos::{Dbscan, KMedias};
+use simulacao::configuracao::{Algoritmo, Configuracao};
+use simulacao::constantes::NUM_INTERACOES;
+use simulacao::enxame::{executar, AlgoritmoEnxame};
+use simulacao::formiga::gerar_formigas;
+use simulacao::grao::{ler_graos_de_arquivo, posicionar_graos, Grao};
+use simulacao::particulas::EnxameParticulas;
+use simulacao::refinamento::refinar;
+use simulacao::restricoes::{Restricoes, Satisfacao};
+use std::env;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 fn main() {
-    let tamanho: (f64, f64) = (64.0, 64.0);
-    let tamanho_mapa: (f64, f64) = tamanho.clone();
+    // `comparar` executa todos os algoritmos no mesmo conjunto de grãos
+    let mut argumentos = env::args().skip(1).peekable();
+    let comparar = matches!(
+        argumentos.peek().map(String::as_str),
+        Some("comparar" | "compare")
+    );
+    if comparar {
+        argumentos.next();
+    }
+
+    // O argumento seguinte, opcional, é um arquivo de cenário com as opções da execução
+    let mut configuracao = match argumentos.next() {
+        Some(caminho) => Configuracao::ler_arquivo(&caminho).unwrap_or_else(|e| {
+            eprintln!("Erro ao ler o cenário {}: {}", caminho, e);
+            std::process::exit(1);
+        }),
+        None => Configuracao::default(),
+    };
+
+    let mut graos = ler_graos_de_arquivo("R15.txt", &configuracao.grade).unwrap();
+
+    // As restrições indicam os grãos pela posição no arquivo, então são lidas logo depois
+    if let Some(caminho) = &configuracao.arquivo_restricoes {
+        match Restricoes::ler_arquivo(caminho, &graos) {
+            Ok(restricoes) => {
+                println!("{} restrições lidas de {}", restricoes.len(), caminho);
+                configuracao.restricoes = Arc::new(restricoes);
+            }
+            Err(e) => {
+                eprintln!("Erro ao ler as restrições {}: {}", caminho, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if configuracao.parametros_automaticos {
+        let parametros = estimar_parametros(&graos);
+        configuracao.aplicar_parametros_automaticos(&parametros);
+        println!(
+            "Parâmetros automáticos: alpha {:.4}, grade {}x{}, {} formigas",
+            configuracao.alpha,
+            configuracao.grade.largura,
+            configuracao.grade.altura,
+            configuracao.num_formigas
+        );
+    }
 
-    // Criar grãos aleatórios igual a 0,01% do tamanho da matriz
-    let quantidade_formigas: i32 = NUM_FORMIGAS;
+    // Distribui os grãos na grade (que pode ter mudado com os parâmetros automáticos)
+    if let Err(e) = posicionar_graos(
+        &mut graos,
+        &configuracao.grade,
+        &configuracao.posicionamento,
+    ) {
+        eprintln!("Erro ao posicionar os grãos: {}", e);
+        std::process::exit(1);
+    }
 
-    let mut cenario: Cenario = Cenario::new(
-        tamanho,
-        gerar_formigas(quantidade_formigas, tamanho_mapa),
-        ler_graos_de_arquivo("R15.txt", tamanho_mapa).unwrap(),
+    if comparar {
+        let mut resultados = vec![];
+        for algoritmo in Algoritmo::TODOS {
+            println!("Executando {}...", algoritmo);
+            let (etapa, refinada) = executar_e_refinar(
+                criar_algoritmo(algoritmo, graos.clone(), &configuracao).as_mut(),
+                &configuracao,
+            );
+            resultados.push((algoritmo.to_string(), etapa));
+            if let Some(refinada) = refinada {
+                resultados.push((format!("{} refinado", algoritmo), refinada));
+            }
+        }
+
+        // Com restrições, uma coluna a mais com quantas cada algoritmo satisfez
+        let com_restricoes = !configuracao.restricoes.is_empty();
+        println!();
+        print!(
+            "{:<20} {:>12} {:>8} {:>12} {:>9} {:>10}",
+            "algoritmo", "agrupamentos", "pureza", "índice Rand", "medida F", "tempo (s)"
+        );
+        if com_restricoes {
+            print!(" {:>11}", "restrições");
+        }
+        println!();
+        for (algoritmo, etapa) in resultados {
+            let avaliacao = etapa.avaliacao;
+            print!(
+                "{:<20} {:>12} {:>8.4} {:>12.4} {:>9.4} {:>10.2}",
+                algoritmo,
+                avaliacao.agrupamentos,
+                avaliacao.pureza,
+                avaliacao.indice_rand,
+                avaliacao.medida_f,
+                etapa.tempo.as_secs_f64()
+            );
+            if let Some(satisfacao) = etapa.satisfacao {
+                print!(
+                    " {:>11}",
+                    format!(
+                        "{}/{}",
+                        satisfacao.juntos + satisfacao.separados,
+                        satisfacao.total_juntos + satisfacao.total_separados
+                    )
+                );
+            }
+            println!();
+        }
+        return;
+    }
+
+    let mut algoritmo = criar_algoritmo(configuracao.algoritmo, graos, &configuracao);
+    let (etapa, refinada) = executar_e_refinar(algoritmo.as_mut(), &configuracao);
+    println!(
+        "Avaliação dos agrupamentos ({}): {}",
+        algoritmo.nome(),
+        etapa.avaliacao
     );
-    cenario.start(NUM_INTERACOES)
+    if let Some(satisfacao) = etapa.satisfacao {
+        println!("{}", satisfacao);
+    }
+    if let (Some(refinada), Some(refinamento)) = (refinada, configuracao.refinamento) {
+        println!(
+            "Avaliação depois do refinamento ({}): {}",
+            refinamento, refinada.avaliacao
+        );
+        if let Some(satisfacao) = refinada.satisfacao {
+            println!("{}", satisfacao);
+        }
+    }
+}
+
+/// Resultado de uma etapa, a execução de um algoritmo ou o refinamento dos agrupamentos
+struct Etapa {
+    avaliacao: Avaliacao,
+    /// Restrições satisfeitas pelos agrupamentos, quando há restrições
+    satisfacao: Option<Satisfacao>,
+    /// Tempo só desta etapa
+    tempo: Duration,
+}
+
+/// Restrições satisfeitas pelos rótulos, quando há restrições
+fn satisfacao(
+    graos: &[Grao],
+    rotulos: &[usize],
+    configuracao: &Configuracao,
+) -> Option<Satisfacao> {
+    Some(&configuracao.restricoes)
+        .filter(|restricoes| !restricoes.is_empty())
+        .map(|restricoes| restricoes.satisfeitas(graos, rotulos))
+}
+
+/// Executa o algoritmo e avalia os agrupamentos; com o refinamento configurado, avalia
+/// também os agrupamentos refinados
+fn executar_e_refinar(
+    algoritmo: &mut dyn AlgoritmoEnxame,
+    configuracao: &Configuracao,
+) -> (Etapa, Option<Etapa>) {
+    let inicio = Instant::now();
+    let avaliacao = executar(algoritmo);
+    let tempo = inicio.elapsed();
+    let (graos, rotulos) = algoritmo.agrupamentos();
+    let etapa = Etapa {
+        avaliacao,
+        satisfacao: satisfacao(&graos, &rotulos, configuracao),
+        tempo,
+    };
+
+    let refinada = configuracao.refinamento.map(|refinamento| {
+        let inicio = Instant::now();
+        let rotulos = refinar(&graos, &rotulos, refinamento, configuracao.grupos);
+        let tempo = inicio.elapsed();
+        Etapa {
+            avaliacao: avaliar(&graos, &rotulos),
+            satisfacao: satisfacao(&graos, &rotulos, configuracao),
+            tempo,
+        }
+    });
+
+    (etapa, refinada)
+}
+
+fn criar_algoritmo(
+    algoritmo: Algoritmo,
+    graos: Vec<Grao>,
+    configuracao: &Configuracao,
+) -> Box<dyn AlgoritmoEnxame> {
+    match algoritmo {
+        Algoritmo::Formigas => Box::new(
+            Cenario::new(
+                gerar_formigas(configuracao.num_formigas, configuracao),
+                graos,
+                configuracao.clone(),
+            )
+            .com_interacoes(NUM_INTERACOES),
+        ),
+        // Os outros algoritmos agrupam os grãos pelos dados, sem usar a grade
+        Algoritmo::AntTree => Box::new(AntTree::new(graos, configuracao.grupos)),
+        Algoritmo::Particulas => Box::new(EnxameParticulas::new(graos, configuracao)),
+        Algoritmo::KMedias => Box::new(KMedias::new(graos, configuracao)),
+        Algoritmo::Dbscan => Box::new(Dbscan::new(graos, configuracao)),
+    }
 }
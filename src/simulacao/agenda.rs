@@ -0,0 +1,253 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Valor de um parâmetro que muda conforme o passo atual da execução. Os passos são
+/// contados desde o início e, depois do último ponto da agenda, o valor fica parado.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Agenda {
+    /// Vai em linha reta de `inicio` no passo 0 até `fim` no passo `duracao`
+    Linear { inicio: f64, fim: f64, duracao: i64 },
+    /// Vai de `inicio` a `fim` multiplicando por um fator constante a cada passo
+    Exponencial { inicio: f64, fim: f64, duracao: i64 },
+    /// Começa em `inicial` e salta para cada valor ao chegar no seu passo
+    Degraus {
+        inicial: f64,
+        mudancas: Vec<(i64, f64)>,
+    },
+    /// Liga os pontos (passo, valor) com retas; antes do primeiro vale o primeiro valor
+    PorPartes { pontos: Vec<(i64, f64)> },
+}
+
+impl Agenda {
+    pub fn valor(&self, passo: i64) -> f64 {
+        match self {
+            Agenda::Linear {
+                inicio,
+                fim,
+                duracao,
+            } => inicio + (fim - inicio) * progresso(passo, *duracao),
+            Agenda::Exponencial {
+                inicio,
+                fim,
+                duracao,
+            } => inicio * (fim / inicio).powf(progresso(passo, *duracao)),
+            Agenda::Degraus { inicial, mudancas } => mudancas
+                .iter()
+                .take_while(|(inicio, _)| *inicio <= passo)
+                .last()
+                .map_or(*inicial, |(_, valor)| *valor),
+            Agenda::PorPartes { pontos } => {
+                let depois = pontos.iter().position(|(inicio, _)| *inicio > passo);
+                match depois {
+                    Some(0) => pontos[0].1,
+                    Some(i) => {
+                        let (p0, v0) = pontos[i - 1];
+                        let (p1, v1) = pontos[i];
+                        v0 + (v1 - v0) * progresso(passo - p0, p1 - p0)
+                    }
+                    None => pontos.last().map_or(0.0, |(_, valor)| *valor),
+                }
+            }
+        }
+    }
+}
+
+/// Fração de `duracao` já percorrida, entre 0 e 1
+fn progresso(passo: i64, duracao: i64) -> f64 {
+    if duracao <= 0 {
+        return 1.0;
+    }
+
+    (passo as f64 / duracao as f64).clamp(0.0, 1.0)
+}
+
+/// Lê as agendas dos arquivos de cenário:
+/// `linear <inicio> <fim> <duracao>`, `exponencial <inicio> <fim> <duracao>`,
+/// `degraus <inicial> <passo>:<valor> ...` e `partes <passo>:<valor> ...`
+impl FromStr for Agenda {
+    type Err = String;
+
+    fn from_str(valor: &str) -> Result<Self, Self::Err> {
+        let mut partes = valor.split_whitespace();
+        let tipo = partes.next().unwrap_or("");
+        let argumentos: Vec<&str> = partes.collect();
+
+        let agenda = match (tipo, argumentos.as_slice()) {
+            ("linear", [inicio, fim, duracao]) => Agenda::Linear {
+                inicio: ler_numero(inicio)?,
+                fim: ler_numero(fim)?,
+                duracao: ler_numero(duracao)?,
+            },
+            ("exponencial", [inicio, fim, duracao]) => {
+                let (inicio, fim): (f64, f64) = (ler_numero(inicio)?, ler_numero(fim)?);
+                if inicio <= 0.0 || fim <= 0.0 {
+                    return Err("a agenda exponencial precisa de valores positivos".to_string());
+                }
+                Agenda::Exponencial {
+                    inicio,
+                    fim,
+                    duracao: ler_numero(duracao)?,
+                }
+            }
+            ("degraus", [inicial, mudancas @ ..]) => Agenda::Degraus {
+                inicial: ler_numero(inicial)?,
+                mudancas: ler_pontos(mudancas)?,
+            },
+            ("partes", pontos) if !pontos.is_empty() => Agenda::PorPartes {
+                pontos: ler_pontos(pontos)?,
+            },
+            _ => return Err(format!("agenda inválida `{}`", valor)),
+        };
+
+        Ok(agenda)
+    }
+}
+
+impl fmt::Display for Agenda {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Agenda::Linear {
+                inicio,
+                fim,
+                duracao,
+            } => write!(f, "linear {} {} {}", inicio, fim, duracao),
+            Agenda::Exponencial {
+                inicio,
+                fim,
+                duracao,
+            } => write!(f, "exponencial {} {} {}", inicio, fim, duracao),
+            Agenda::Degraus { inicial, mudancas } => {
+                write!(f, "degraus {}", inicial)?;
+                escrever_pontos(f, mudancas)
+            }
+            Agenda::PorPartes { pontos } => {
+                write!(f, "partes")?;
+                escrever_pontos(f, pontos)
+            }
+        }
+    }
+}
+
+fn ler_numero<T: FromStr>(valor: &str) -> Result<T, String> {
+    valor
+        .replace('_', "")
+        .parse()
+        .map_err(|_| format!("número inválido `{}`", valor))
+}
+
+/// Lê pontos `passo:valor`, que precisam estar em ordem crescente de passo
+fn ler_pontos(pontos: &[&str]) -> Result<Vec<(i64, f64)>, String> {
+    let pontos = pontos
+        .iter()
+        .map(|ponto| {
+            let (passo, valor) = ponto
+                .split_once(':')
+                .ok_or_else(|| format!("esperado `passo:valor`, encontrado `{}`", ponto))?;
+            Ok((ler_numero(passo)?, ler_numero(valor)?))
+        })
+        .collect::<Result<Vec<(i64, f64)>, String>>()?;
+
+    if pontos.windows(2).any(|par| par[0].0 >= par[1].0) {
+        return Err("os passos da agenda precisam ser crescentes".to_string());
+    }
+
+    Ok(pontos)
+}
+
+fn escrever_pontos(f: &mut fmt::Formatter, pontos: &[(i64, f64)]) -> fmt::Result {
+    for (passo, valor) in pontos {
+        write!(f, " {}:{}", passo, valor)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agenda(texto: &str) -> Agenda {
+        texto.parse().unwrap()
+    }
+
+    fn perto(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn linear() {
+        let agenda = agenda("linear 0.1 0.5 1_000");
+        assert!(perto(agenda.valor(0), 0.1));
+        assert!(perto(agenda.valor(250), 0.2));
+        assert!(perto(agenda.valor(1000), 0.5));
+        // Parada depois do fim
+        assert!(perto(agenda.valor(5000), 0.5));
+    }
+
+    #[test]
+    fn exponencial() {
+        let agenda = agenda("exponencial 1 100 200");
+        assert!(perto(agenda.valor(0), 1.0));
+        assert!(perto(agenda.valor(100), 10.0));
+        assert!(perto(agenda.valor(200), 100.0));
+        assert!(perto(agenda.valor(400), 100.0));
+    }
+
+    #[test]
+    fn degraus() {
+        let agenda = agenda("degraus 1 100:2 300:3");
+        assert_eq!(agenda.valor(0), 1.0);
+        assert_eq!(agenda.valor(99), 1.0);
+        assert_eq!(agenda.valor(100), 2.0);
+        assert_eq!(agenda.valor(299), 2.0);
+        assert_eq!(agenda.valor(300), 3.0);
+        assert_eq!(agenda.valor(10_000), 3.0);
+    }
+
+    #[test]
+    fn por_partes() {
+        let agenda = agenda("partes 100:1 200:3 400:2");
+        // Antes do primeiro ponto vale o primeiro valor
+        assert_eq!(agenda.valor(0), 1.0);
+        assert!(perto(agenda.valor(150), 2.0));
+        assert!(perto(agenda.valor(200), 3.0));
+        assert!(perto(agenda.valor(300), 2.5));
+        // Depois do último fica parado
+        assert_eq!(agenda.valor(400), 2.0);
+        assert_eq!(agenda.valor(1_000), 2.0);
+    }
+
+    #[test]
+    fn texto_da_agenda_le_a_mesma_agenda() {
+        for texto in [
+            "linear 0.1 0.5 1000",
+            "exponencial 1 100 200",
+            "degraus 1 100:2 300:3",
+            "partes 100:1 200:3",
+        ] {
+            let agenda = agenda(texto);
+            assert_eq!(agenda.to_string(), texto);
+            assert_eq!(agenda.to_string().parse(), Ok(agenda));
+        }
+    }
+
+    #[test]
+    fn rejeita_agendas_invalidas() {
+        for texto in [
+            "",
+            "linear 0.1 0.5",
+            "linear a 0.5 10",
+            "exponencial 0 1 10",
+            "exponencial 1 -1 10",
+            "degraus",
+            "degraus 1 100",
+            "degraus 1 200:2 100:3",
+            "partes",
+            "partes 100:1 100:2",
+            "partes 200:1 100:2",
+            "seno 1 2 3",
+        ] {
+            assert!(texto.parse::<Agenda>().is_err(), "{}", texto);
+        }
+    }
+}
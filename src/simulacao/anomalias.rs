@@ -0,0 +1,125 @@
+use super::configuracao::{Configuracao, ParametrosPasso};
+use super::densidade::similaridade;
+use super::grao::Grao;
+use super::mapa::Mapa;
+
+/// Grão candidato a outlier, com a sua vizinhança no mapa final
+#[derive(Clone, Debug)]
+pub struct Anomalia {
+    pub grao: Grao,
+    /// Outros grãos na vizinhança do grão
+    pub vizinhos: usize,
+    /// Similaridade local do grão pela função de densidade configurada
+    pub similaridade: f64,
+    /// `1 / (1 + similaridade)`: 1 para um grão isolado, menor quanto mais ele combina com
+    /// a vizinhança
+    pub pontuacao: f64,
+}
+
+/// Pontua todos os grãos do mapa e os ordena do mais para o menos anômalo. Entre grãos de
+/// mesma pontuação vêm primeiro os com menos vizinhos.
+pub fn pontuar_anomalias(
+    mapa: &Mapa,
+    configuracao: &Configuracao,
+    parametros: &ParametrosPasso,
+) -> Vec<Anomalia> {
+    let mut anomalias: Vec<Anomalia> = mapa
+        .graos()
+        .into_iter()
+        .map(|grao| {
+            // O próprio grão sai da vizinhança para que um grão isolado tenha similaridade 0
+            // em qualquer função de densidade. No modelo de pilhas a pilha dele entra pelo
+            // centroide dos outros grãos dela, que é com quem ele é comparado.
+            let mut vizinhanca = mapa.graos_redor(
+                &grao.posicao,
+                parametros.raio_vizinhanca,
+                configuracao.formato_vizinhanca,
+                configuracao.sigma_vizinhanca,
+                Some(grao.id),
+            );
+            vizinhanca
+                .graos
+                .retain(|(vizinho, _)| vizinho.id != grao.id);
+            let vizinhos = vizinhanca.graos.len();
+            let similaridade = similaridade(&grao, &vizinhanca, configuracao, parametros).max(0.0);
+
+            Anomalia {
+                grao,
+                vizinhos,
+                similaridade,
+                pontuacao: 1.0 / (1.0 + similaridade),
+            }
+        })
+        .collect();
+
+    anomalias.sort_by(|a, b| {
+        b.pontuacao
+            .total_cmp(&a.pontuacao)
+            .then(a.vizinhos.cmp(&b.vizinhos))
+    });
+
+    anomalias
+}
+
+/// Salva a lista ordenada num CSV separado por `;`
+pub fn salvar_anomalias(
+    path: &str,
+    anomalias: &[Anomalia],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut conteudo = String::from("posicao;x;y;grupo;vizinhos;similaridade;pontuacao\n");
+    for (posicao, anomalia) in anomalias.iter().enumerate() {
+        conteudo.push_str(&format!(
+            "{};{};{};{};{};{:.6};{:.6}\n",
+            posicao + 1,
+            anomalia.grao.posicao.x,
+            anomalia.grao.posicao.y,
+            anomalia.grao.grupo,
+            anomalia.vizinhos,
+            anomalia.similaridade,
+            anomalia.pontuacao
+        ));
+    }
+
+    std::fs::write(path, conteudo)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::formiga::Perfil;
+    use crate::simulacao::grade::{Fronteira, Grade, Topologia};
+    use crate::simulacao::outros::Ponto;
+
+    fn grao(x: i32, y: i32, valor: f64) -> Grao {
+        Grao::new(Ponto { x, y }, vec![valor], 0)
+    }
+
+    #[test]
+    fn grao_isolado_e_mais_anomalo_que_grao_numa_pilha_parecida() {
+        let grade = Grade::new(10, 10, Fronteira::Toroidal, Topologia::Quadrada);
+        let configuracao = Configuracao {
+            grade,
+            pilhas: true,
+            ..Configuracao::default()
+        };
+        let isolado = grao(0, 0, 0.5);
+        let pilha = [grao(5, 5, 0.1), grao(5, 5, 0.12), grao(5, 5, 0.11)];
+        let mut graos = vec![isolado.clone()];
+        graos.extend(pilha.iter().cloned());
+        let mut mapa = Mapa::new(grade, graos);
+        mapa.ativar_pilhas();
+
+        let perfil = Perfil::default();
+        let parametros = configuracao.parametros_no_passo(0, configuracao.alpha, &perfil);
+        let anomalias = pontuar_anomalias(&mapa, &configuracao, &parametros);
+
+        assert_eq!(anomalias.len(), 4);
+        assert_eq!(anomalias[0].grao.id, isolado.id);
+        assert_eq!(anomalias[0].pontuacao, 1.0);
+        for anomalia in &anomalias[1..] {
+            assert_eq!(anomalia.vizinhos, 1);
+            assert!(anomalia.pontuacao < 1.0);
+        }
+    }
+}
@@ -0,0 +1,407 @@
+use super::densidade::distancia_entre_par_de_dados;
+use super::enxame::AlgoritmoEnxame;
+use super::grao::Grao;
+use std::collections::HashSet;
+
+/// Árvore do AntTree (Azzag et al.), na variante sem limiares: cada grão é uma formiga
+/// que parte do suporte e desce pela árvore até se prender a uma formiga (ou ao suporte).
+/// As subárvores presas ao suporte são os agrupamentos naturais.
+#[derive(Clone, Debug)]
+pub struct ArvoreFormigas {
+    /// Pai de cada grão; `None` para os presos diretamente ao suporte
+    pub pais: Vec<Option<usize>>,
+    /// Filhos de cada grão
+    pub filhos: Vec<Vec<usize>>,
+    /// Grãos presos ao suporte
+    pub raizes: Vec<usize>,
+    /// Maior distância entre dois grãos, que normaliza a similaridade
+    distancia_maxima: f64,
+}
+
+/// Posição de uma formiga durante a construção da árvore
+#[derive(Clone, Copy, PartialEq)]
+enum No {
+    Suporte,
+    Formiga(usize),
+}
+
+impl ArvoreFormigas {
+    /// Constrói a árvore com as formigas em ordem decrescente de similaridade média.
+    ///
+    /// Em cada nó, enquanto ele tem menos de dois filhos a formiga se prende a ele. Senão
+    /// ela compara a similaridade com o filho mais parecido (`a+`) com a menor similaridade
+    /// entre os filhos do nó (`TDissim`): se for menor, a formiga é diferente de todos e se
+    /// prende ao nó; caso contrário desce para `a+`.
+    pub fn construir(graos: &[Grao]) -> ArvoreFormigas {
+        let n = graos.len();
+        let mut arvore = ArvoreFormigas {
+            pais: vec![None; n],
+            filhos: vec![vec![]; n],
+            raizes: vec![],
+            distancia_maxima: 0.0,
+        };
+
+        for i in 0..n {
+            for j in i + 1..n {
+                let distancia = distancia_entre_par_de_dados(&graos[i].dados, &graos[j].dados);
+                arvore.distancia_maxima = arvore.distancia_maxima.max(distancia);
+            }
+        }
+
+        let media: Vec<f64> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .filter(|&j| j != i)
+                    .map(|j| arvore.similaridade(graos, i, j))
+                    .sum::<f64>()
+                    / (n.max(2) - 1) as f64
+            })
+            .collect();
+        let mut ordem: Vec<usize> = (0..n).collect();
+        ordem.sort_by(|&a, &b| media[b].total_cmp(&media[a]));
+
+        // Menor similaridade entre os filhos de cada nó, guardada até o nó ganhar um filho
+        let mut tdissim_suporte: Option<f64> = None;
+        let mut tdissim: Vec<Option<f64>> = vec![None; n];
+
+        for formiga in ordem {
+            let mut posicao = No::Suporte;
+
+            loop {
+                let filhos = match posicao {
+                    No::Suporte => &arvore.raizes,
+                    No::Formiga(no) => &arvore.filhos[no],
+                };
+
+                let prender = if filhos.len() < 2 {
+                    None
+                } else {
+                    let cache = match posicao {
+                        No::Suporte => &mut tdissim_suporte,
+                        No::Formiga(no) => &mut tdissim[no],
+                    };
+                    let limite =
+                        *cache.get_or_insert_with(|| arvore.menor_similaridade(graos, filhos));
+
+                    let (mais_parecido, similaridade) = filhos
+                        .iter()
+                        .map(|&filho| (filho, arvore.similaridade(graos, formiga, filho)))
+                        .max_by(|a, b| a.1.total_cmp(&b.1))
+                        .expect("o nó tem filhos");
+
+                    if similaridade < limite {
+                        None
+                    } else {
+                        Some(mais_parecido)
+                    }
+                };
+
+                match prender {
+                    Some(mais_parecido) => posicao = No::Formiga(mais_parecido),
+                    None => {
+                        match posicao {
+                            No::Suporte => {
+                                arvore.raizes.push(formiga);
+                                tdissim_suporte = None;
+                            }
+                            No::Formiga(no) => {
+                                arvore.filhos[no].push(formiga);
+                                arvore.pais[formiga] = Some(no);
+                                tdissim[no] = None;
+                            }
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+
+        arvore
+    }
+
+    /// Similaridade entre dois grãos, entre 0 e 1
+    fn similaridade(&self, graos: &[Grao], a: usize, b: usize) -> f64 {
+        if self.distancia_maxima <= 0.0 {
+            return 1.0;
+        }
+
+        1.0 - distancia_entre_par_de_dados(&graos[a].dados, &graos[b].dados) / self.distancia_maxima
+    }
+
+    fn menor_similaridade(&self, graos: &[Grao], nos: &[usize]) -> f64 {
+        let mut menor = f64::INFINITY;
+        for (i, &a) in nos.iter().enumerate() {
+            for &b in &nos[i + 1..] {
+                menor = menor.min(self.similaridade(graos, a, b));
+            }
+        }
+
+        menor
+    }
+
+    /// Grãos da subárvore de `no`, incluindo ele
+    pub fn subarvore(&self, no: usize) -> Vec<usize> {
+        let mut resultado = vec![];
+        let mut pilha = vec![no];
+        while let Some(atual) = pilha.pop() {
+            resultado.push(atual);
+            pilha.extend(&self.filhos[atual]);
+        }
+
+        resultado
+    }
+
+    /// Corta a árvore em agrupamentos e retorna um rótulo por grão. Sem `k` cada subárvore
+    /// do suporte é um agrupamento. Com `k`, enquanto houver poucos agrupamentos o maior é
+    /// dividido nos filhos da sua raiz (ou, se nenhum se ramifica, perde o grão menos
+    /// parecido com a raiz), e enquanto houver muitos o menor é unido ao de raiz mais
+    /// parecida. Para `k` até o número de grãos o resultado tem exatamente `k` rótulos.
+    pub fn cortar(&self, graos: &[Grao], k: Option<usize>) -> Vec<usize> {
+        // Cada agrupamento: o nó onde ele pode ser dividido, os seus grãos e se eles ainda
+        // são a subárvore desse nó mais grãos unidos de fora (e podem ser divididos por ela)
+        let mut agrupamentos: Vec<(usize, Vec<usize>, bool)> = self
+            .raizes
+            .iter()
+            .map(|&raiz| (self.ramificacao(raiz), self.subarvore(raiz), true))
+            .collect();
+
+        if let Some(k) = k.filter(|k| *k > 0) {
+            while agrupamentos.len() < k {
+                let maior = agrupamentos
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (no, _, pela_arvore))| *pela_arvore && self.filhos[*no].len() >= 2)
+                    .max_by_key(|(_, (_, membros, _))| membros.len())
+                    .map(|(indice, _)| indice);
+                let indice = match maior {
+                    Some(indice) => indice,
+                    None => {
+                        if !self.separar_grao(graos, &mut agrupamentos) {
+                            break;
+                        }
+                        continue;
+                    }
+                };
+                let (no, membros, _) = agrupamentos.swap_remove(indice);
+
+                let mut novos: Vec<(usize, Vec<usize>)> = self.filhos[no]
+                    .iter()
+                    .map(|&filho| (filho, self.subarvore(filho)))
+                    .collect();
+
+                // O próprio nó e os grãos unidos antes vão para o filho mais parecido
+                let nos_filhos: HashSet<usize> = novos
+                    .iter()
+                    .flat_map(|(_, membros)| membros.clone())
+                    .collect();
+                for grao in membros.into_iter().filter(|g| !nos_filhos.contains(g)) {
+                    let destino = (0..novos.len())
+                        .max_by(|&a, &b| {
+                            self.similaridade(graos, grao, novos[a].0)
+                                .total_cmp(&self.similaridade(graos, grao, novos[b].0))
+                        })
+                        .expect("o nó tem filhos");
+                    novos[destino].1.push(grao);
+                }
+
+                agrupamentos.extend(
+                    novos
+                        .into_iter()
+                        .map(|(filho, membros)| (self.ramificacao(filho), membros, true)),
+                );
+            }
+
+            while agrupamentos.len() > k {
+                let menor = (0..agrupamentos.len())
+                    .min_by_key(|&i| agrupamentos[i].1.len())
+                    .expect("há agrupamentos");
+                let (no, membros, _) = agrupamentos.swap_remove(menor);
+
+                let destino = (0..agrupamentos.len())
+                    .max_by(|&a, &b| {
+                        self.similaridade(graos, no, agrupamentos[a].0)
+                            .total_cmp(&self.similaridade(graos, no, agrupamentos[b].0))
+                    })
+                    .expect("há agrupamentos");
+                agrupamentos[destino].1.extend(membros);
+            }
+        }
+
+        let mut rotulos = vec![0; graos.len()];
+        for (rotulo, (_, membros, _)) in agrupamentos.iter().enumerate() {
+            for &grao in membros {
+                rotulos[grao] = rotulo;
+            }
+        }
+
+        rotulos
+    }
+
+    /// Tira do maior agrupamento com mais de um grão o grão menos parecido com o seu nó e o
+    /// põe num agrupamento próprio. Nenhum dos dois segue mais a forma da árvore. Retorna
+    /// `false` se todos os agrupamentos têm um grão só.
+    fn separar_grao(
+        &self,
+        graos: &[Grao],
+        agrupamentos: &mut Vec<(usize, Vec<usize>, bool)>,
+    ) -> bool {
+        let maior = agrupamentos
+            .iter_mut()
+            .filter(|(_, membros, _)| membros.len() >= 2)
+            .max_by_key(|(_, membros, _)| membros.len());
+        let (no, membros, pela_arvore) = match maior {
+            Some(agrupamento) => agrupamento,
+            None => return false,
+        };
+
+        let posicao = (0..membros.len())
+            .filter(|&i| membros[i] != *no)
+            .min_by(|&a, &b| {
+                self.similaridade(graos, membros[a], *no)
+                    .total_cmp(&self.similaridade(graos, membros[b], *no))
+            })
+            .expect("o agrupamento tem outro grão além do nó");
+        let grao = membros.swap_remove(posicao);
+        *pela_arvore = false;
+
+        agrupamentos.push((grao, vec![grao], false));
+        true
+    }
+
+    /// Desce pelos nós de filho único até o primeiro nó que se ramifica (ou uma folha)
+    fn ramificacao(&self, mut no: usize) -> usize {
+        while self.filhos[no].len() == 1 {
+            no = self.filhos[no][0];
+        }
+
+        no
+    }
+
+    /// Salva a árvore num CSV separado por `;`, com o pai de cada grão (`-1` para o
+    /// suporte), o seu grupo e o agrupamento do corte
+    pub fn salvar(
+        &self,
+        path: &str,
+        graos: &[Grao],
+        rotulos: &[usize],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut conteudo = String::from("grao;pai;grupo;agrupamento\n");
+        for (indice, grao) in graos.iter().enumerate() {
+            let pai = self.pais[indice].map_or(-1, |pai| pai as i64);
+            conteudo.push_str(&format!(
+                "{};{};{};{}\n",
+                indice, pai, grao.grupo, rotulos[indice]
+            ));
+        }
+
+        std::fs::write(path, conteudo)?;
+        Ok(())
+    }
+}
+
+/// O AntTree como algoritmo de enxame: a árvore é construída e cortada num único passo
+/// e salva em `arvore.csv`
+#[derive(Clone, Debug)]
+pub struct AntTree {
+    graos: Vec<Grao>,
+    grupos: Option<usize>,
+    rotulos: Option<Vec<usize>>,
+}
+
+impl AntTree {
+    pub fn new(graos: Vec<Grao>, grupos: Option<usize>) -> AntTree {
+        AntTree {
+            graos,
+            grupos,
+            rotulos: None,
+        }
+    }
+}
+
+impl AlgoritmoEnxame for AntTree {
+    fn nome(&self) -> &'static str {
+        "AntTree"
+    }
+
+    fn inicializar(&mut self) {
+        self.rotulos = None;
+    }
+
+    fn passo(&mut self) {
+        let arvore = ArvoreFormigas::construir(&self.graos);
+        let rotulos = arvore.cortar(&self.graos, self.grupos);
+        if let Err(e) = arvore.salvar("arvore.csv", &self.graos, &rotulos) {
+            eprintln!("Erro ao salvar a árvore: {}", e);
+        }
+        self.rotulos = Some(rotulos);
+    }
+
+    fn terminou(&self) -> bool {
+        self.rotulos.is_some()
+    }
+
+    fn agrupamentos(&self) -> (Vec<Grao>, Vec<usize>) {
+        (
+            self.graos.clone(),
+            self.rotulos
+                .clone()
+                .unwrap_or_else(|| vec![0; self.graos.len()]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::outros::Ponto;
+
+    /// Três nuvens de dez grãos em torno de (0, 0), (10, 0) e (0, 10)
+    fn graos() -> Vec<Grao> {
+        let centros = [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)];
+        centros
+            .iter()
+            .enumerate()
+            .flat_map(|(grupo, (cx, cy))| {
+                (0..10).map(move |i| {
+                    let angulo = i as f64;
+                    let raio = 0.1 * (i % 4) as f64;
+                    Grao::new(
+                        Ponto { x: 0, y: 0 },
+                        vec![cx + raio * angulo.cos(), cy + raio * angulo.sin()],
+                        grupo as i32,
+                    )
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn corte_gera_exatamente_k_agrupamentos() {
+        let graos = graos();
+        let arvore = ArvoreFormigas::construir(&graos);
+
+        for k in 1..=graos.len() {
+            let rotulos = arvore.cortar(&graos, Some(k));
+            assert_eq!(rotulos.len(), graos.len());
+
+            let mut distintos = rotulos.clone();
+            distintos.sort();
+            distintos.dedup();
+            assert_eq!(distintos.len(), k, "corte em {}", k);
+            assert_eq!(distintos, (0..k).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn todo_grao_chega_ao_suporte() {
+        let graos = graos();
+        let arvore = ArvoreFormigas::construir(&graos);
+
+        let alcancados: usize = arvore
+            .raizes
+            .iter()
+            .map(|&raiz| arvore.subarvore(raiz).len())
+            .sum();
+        assert_eq!(alcancados, graos.len());
+    }
+}
@@ -0,0 +1,48 @@
+use super::constantes::AMOSTRA_PARAMETROS;
+use super::densidade::distancia_entre_par_de_dados;
+use super::grao::Grao;
+use rand::seq::SliceRandom;
+
+/// Parâmetros da execução derivados dos grãos carregados
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParametrosAutomaticos {
+    /// Média das distâncias entre pares de uma amostra dos grãos
+    pub alpha: f64,
+    /// Lado da grade quadrada, cerca de √(10·n)
+    pub lado_grade: i32,
+    /// Cerca de n/10 formigas
+    pub num_formigas: i32,
+}
+
+/// Estima alpha, tamanho da grade e número de formigas a partir dos grãos
+pub fn estimar_parametros(graos: &[Grao]) -> ParametrosAutomaticos {
+    let n = graos.len();
+
+    // As distâncias são calculadas entre todos os pares de uma amostra dos grãos
+    let mut rng = rand::thread_rng();
+    let amostra: Vec<&Grao> = graos
+        .choose_multiple(&mut rng, AMOSTRA_PARAMETROS)
+        .collect();
+
+    let mut soma = 0.0;
+    let mut pares = 0;
+    for (i, a) in amostra.iter().enumerate() {
+        for b in &amostra[i + 1..] {
+            soma += distancia_entre_par_de_dados(&a.dados, &b.dados);
+            pares += 1;
+        }
+    }
+
+    // Sem pares para comparar (ou com todos os grãos iguais) o alpha não pode ser zero
+    let alpha = if pares > 0 && soma > 0.0 {
+        soma / pares as f64
+    } else {
+        1.0
+    };
+
+    ParametrosAutomaticos {
+        alpha,
+        lado_grade: ((10.0 * n as f64).sqrt().ceil() as i32).max(1),
+        num_formigas: ((n as f64 / 10.0).round() as i32).max(1),
+    }
+}
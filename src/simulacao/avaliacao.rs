@@ -0,0 +1,219 @@
+use super::grade::Grade;
+use super::grao::Grao;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Qualidade de um agrupamento em relação aos grupos verdadeiros dos grãos
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Avaliacao {
+    /// Número de agrupamentos encontrados
+    pub agrupamentos: usize,
+    /// Fração dos grãos que pertencem ao grupo mais comum do seu agrupamento
+    pub pureza: f64,
+    /// Fração dos pares de grãos em que agrupamento e grupo concordam (juntos ou separados)
+    pub indice_rand: f64,
+    /// Média, ponderada pelo tamanho, da melhor medida F de cada grupo verdadeiro
+    pub medida_f: f64,
+}
+
+impl fmt::Display for Avaliacao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} agrupamentos, pureza {:.4}, índice Rand {:.4}, medida F {:.4}",
+            self.agrupamentos, self.pureza, self.indice_rand, self.medida_f
+        )
+    }
+}
+
+/// Compara os rótulos (um por grão, na mesma ordem) com o campo `grupo` dos grãos
+pub fn avaliar(graos: &[Grao], rotulos: &[usize]) -> Avaliacao {
+    let n = graos.len().min(rotulos.len());
+
+    // Tabela de contingência: (agrupamento, grupo) -> quantidade
+    let mut tabela: HashMap<(usize, i32), usize> = HashMap::new();
+    let mut por_agrupamento: HashMap<usize, usize> = HashMap::new();
+    let mut por_grupo: HashMap<i32, usize> = HashMap::new();
+    for (grao, rotulo) in graos.iter().zip(rotulos) {
+        *tabela.entry((*rotulo, grao.grupo)).or_insert(0) += 1;
+        *por_agrupamento.entry(*rotulo).or_insert(0) += 1;
+        *por_grupo.entry(grao.grupo).or_insert(0) += 1;
+    }
+
+    if n == 0 {
+        return Avaliacao {
+            agrupamentos: 0,
+            pureza: 0.0,
+            indice_rand: 0.0,
+            medida_f: 0.0,
+        };
+    }
+
+    let pureza = por_agrupamento
+        .keys()
+        .map(|rotulo| {
+            tabela
+                .iter()
+                .filter(|((r, _), _)| r == rotulo)
+                .map(|(_, quantidade)| *quantidade)
+                .max()
+                .unwrap_or(0)
+        })
+        .sum::<usize>() as f64
+        / n as f64;
+
+    let pares = |k: usize| (k * k.saturating_sub(1) / 2) as f64;
+    let total = pares(n);
+    let juntos_nos_dois: f64 = tabela.values().map(|&k| pares(k)).sum();
+    let juntos_no_agrupamento: f64 = por_agrupamento.values().map(|&k| pares(k)).sum();
+    let juntos_no_grupo: f64 = por_grupo.values().map(|&k| pares(k)).sum();
+    let indice_rand = if total > 0.0 {
+        (total - juntos_no_agrupamento - juntos_no_grupo + 2.0 * juntos_nos_dois) / total
+    } else {
+        1.0
+    };
+
+    let medida_f = por_grupo
+        .iter()
+        .map(|(grupo, &tamanho_grupo)| {
+            let melhor = por_agrupamento
+                .iter()
+                .map(|(rotulo, &tamanho_agrupamento)| {
+                    let comum = *tabela.get(&(*rotulo, *grupo)).unwrap_or(&0) as f64;
+                    if comum == 0.0 {
+                        return 0.0;
+                    }
+                    let precisao = comum / tamanho_agrupamento as f64;
+                    let revocacao = comum / tamanho_grupo as f64;
+                    2.0 * precisao * revocacao / (precisao + revocacao)
+                })
+                .fold(0.0, f64::max);
+            tamanho_grupo as f64 / n as f64 * melhor
+        })
+        .sum();
+
+    Avaliacao {
+        agrupamentos: por_agrupamento.len(),
+        pureza,
+        indice_rand,
+        medida_f,
+    }
+}
+
+/// Agrupamentos formados na grade: cada conjunto de células ocupadas ligadas por células
+/// vizinhas é um agrupamento. Retorna um rótulo por grão, na ordem de `graos`.
+pub fn rotulos_da_grade(graos: &[Grao], grade: &Grade) -> Vec<usize> {
+    let mut ocupadas: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+    for (indice, grao) in graos.iter().enumerate() {
+        ocupadas
+            .entry((grao.posicao.x, grao.posicao.y))
+            .or_default()
+            .push(indice);
+    }
+
+    let mut rotulos = vec![usize::MAX; graos.len()];
+    let mut proximo_rotulo = 0;
+
+    for indice in 0..graos.len() {
+        if rotulos[indice] != usize::MAX {
+            continue;
+        }
+
+        // Busca em profundidade pelas células ocupadas vizinhas
+        let mut pilha = vec![graos[indice].posicao];
+        while let Some(celula) = pilha.pop() {
+            let na_celula = match ocupadas.get(&(celula.x, celula.y)) {
+                Some(na_celula) => na_celula,
+                None => continue,
+            };
+            if rotulos[na_celula[0]] != usize::MAX {
+                continue;
+            }
+
+            for &grao in na_celula {
+                rotulos[grao] = proximo_rotulo;
+            }
+            pilha.extend(
+                grade
+                    .vizinhanca(&celula, 1)
+                    .into_iter()
+                    .filter(|(_, distancia)| *distancia == 1)
+                    .map(|(vizinho, _)| vizinho),
+            );
+        }
+
+        proximo_rotulo += 1;
+    }
+
+    rotulos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::grade::{Fronteira, Topologia};
+    use crate::simulacao::outros::Ponto;
+
+    fn grao(x: i32, y: i32, grupo: i32) -> Grao {
+        Grao::new(Ponto { x, y }, vec![0.0], grupo)
+    }
+
+    fn graos_dos_grupos(grupos: &[i32]) -> Vec<Grao> {
+        grupos.iter().map(|&grupo| grao(0, 0, grupo)).collect()
+    }
+
+    fn perto(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn rotulos_iguais_aos_grupos_dao_nota_maxima() {
+        let graos = graos_dos_grupos(&[1, 1, 1, 2, 2, 2]);
+        let avaliacao = avaliar(&graos, &[5, 5, 5, 9, 9, 9]);
+
+        assert_eq!(avaliacao.agrupamentos, 2);
+        assert!(perto(avaliacao.pureza, 1.0));
+        assert!(perto(avaliacao.indice_rand, 1.0));
+        assert!(perto(avaliacao.medida_f, 1.0));
+    }
+
+    #[test]
+    fn divisao_conhecida() {
+        // Contingência: agrupamento 0 = {2 do grupo 1}, agrupamento 1 = {1 do grupo 1, 3 do
+        // grupo 2}
+        let graos = graos_dos_grupos(&[1, 1, 1, 2, 2, 2]);
+        let avaliacao = avaliar(&graos, &[0, 0, 1, 1, 1, 1]);
+
+        assert_eq!(avaliacao.agrupamentos, 2);
+        // (2 + 3) / 6
+        assert!(perto(avaliacao.pureza, 5.0 / 6.0));
+        // 15 pares, 7 juntos nos agrupamentos, 6 nos grupos e 4 nos dois: (15 - 7 - 6 + 8) / 15
+        assert!(perto(avaliacao.indice_rand, 2.0 / 3.0));
+        // Grupo 1: melhor F 0,8 (agrupamento 0); grupo 2: 6/7 (agrupamento 1)
+        assert!(perto(avaliacao.medida_f, 0.5 * 0.8 + 0.5 * 6.0 / 7.0));
+    }
+
+    #[test]
+    fn regioes_conectadas_da_grade() {
+        let grade = Grade::new(10, 10, Fronteira::Limitada, Topologia::Quadrada);
+        let graos = vec![
+            grao(1, 1, 1),
+            grao(1, 2, 1),
+            grao(2, 3, 1),
+            grao(6, 6, 2),
+            grao(7, 7, 2),
+            grao(7, 7, 2),
+        ];
+        let rotulos = rotulos_da_grade(&graos, &grade);
+
+        assert_eq!(rotulos[0], rotulos[1]);
+        assert_eq!(rotulos[1], rotulos[2]);
+        assert_eq!(rotulos[3], rotulos[4]);
+        assert_eq!(rotulos[4], rotulos[5]);
+        assert_ne!(rotulos[0], rotulos[3]);
+
+        let avaliacao = avaliar(&graos, &rotulos);
+        assert_eq!(avaliacao.agrupamentos, 2);
+        assert!(perto(avaliacao.pureza, 1.0));
+    }
+}
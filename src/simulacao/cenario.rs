@@ -0,0 +1,785 @@
+use super::anomalias::{pontuar_anomalias, salvar_anomalias};
+use super::avaliacao::rotulos_da_grade;
+use super::configuracao::{Configuracao, ModoAtualizacao};
+use super::constantes::{NUM_INTERACOES, PASSOS_POR_LOTE};
+use super::enxame::AlgoritmoEnxame;
+use super::estagnacao::RegraEstagnacao;
+use super::formiga::{Formiga, Perfil, Proposta};
+use super::grade::{Grade, Topologia};
+use super::grao::Grao;
+use super::mapa::Mapa;
+use super::outros::Ponto;
+use super::trabalhadores::Trabalhadores;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::thread;
+
+use image::{ImageBuffer, Rgb};
+use imageproc::drawing::{draw_filled_circle_mut, draw_hollow_circle_mut, draw_polygon_mut};
+use imageproc::point::Point;
+use imageproc::rect::Rect;
+use rand::Rng; // Necessário para desenhar retângulos
+
+#[derive(Debug)]
+pub struct Cenario {
+    grade: Grade,
+    formigas: Vec<Formiga>,
+    mapa: Arc<Mapa>,
+    cores_por_grupo: HashMap<String, Rgb<u8>>,
+    configuracao: Arc<Configuracao>,
+    /// Threads de trabalho do modo assíncrono, criadas no primeiro lote
+    trabalhadores: Option<Trabalhadores>,
+    /// Passos das formigas da execução e quantos já foram executados
+    numero_interacoes: i64,
+    passos_executados: i64,
+    /// Controla as imagens de progresso já geradas
+    gerou_percentuais: [bool; 2],
+    contador_img: i64,
+    /// Evolução do alpha adaptativo: (passos, média, mínimo, máximo)
+    historico_alpha: Vec<(i64, f64, f64, f64)>,
+    concluido: bool,
+    /// Células dos grãos destacados como outliers na imagem final
+    destaques: Vec<Ponto>,
+}
+
+/// Porcentagens de passos restantes em que são geradas imagens de progresso
+const PORCENTAGENS_IMAGENS: [f64; 2] = [1.0, 0.0];
+
+impl Cenario {
+    /// Inicializa um novo cenário e cria o mapa de cores para os grupos de grãos
+    pub fn new(mut formigas: Vec<Formiga>, graos: Vec<Grao>, configuracao: Configuracao) -> Self {
+        let mut cores_por_grupo = HashMap::new();
+
+        // Inicializa o mapa de cores para os grupos de grãos
+        for grao in &graos {
+            cores_por_grupo
+                .entry(format!("{:?}", grao.grupo))
+                .or_insert_with(gerar_cor_aleatoria);
+        }
+
+        let mut mapa = Mapa::new(configuracao.grade, graos);
+        if configuracao.feromonio {
+            mapa.ativar_feromonio();
+        }
+        if configuracao.pilhas {
+            mapa.ativar_pilhas();
+        }
+        if configuracao.uma_formiga_por_celula {
+            mapa.ativar_ocupacao();
+            posicionar_formigas(&mut formigas, &mapa);
+        }
+
+        Cenario {
+            grade: configuracao.grade,
+            formigas,
+            mapa: Arc::new(mapa),
+            cores_por_grupo, // Armazena o mapa de cores
+            configuracao: Arc::new(configuracao),
+            trabalhadores: None,
+            numero_interacoes: NUM_INTERACOES,
+            passos_executados: 0,
+            gerou_percentuais: [false; 2],
+            contador_img: 0,
+            historico_alpha: vec![],
+            concluido: false,
+            destaques: vec![],
+        }
+    }
+
+    /// Define quantos passos das formigas a execução terá (`NUM_INTERACOES` por padrão)
+    pub fn com_interacoes(mut self, numero_interacoes: i64) -> Self {
+        self.numero_interacoes = numero_interacoes;
+        self
+    }
+
+    /// Fecha a execução depois do último lote: fase de finalização, relatórios e imagem final
+    fn concluir(&mut self) {
+        println!("Passos executados: {}", self.passos_executados);
+
+        if !self.historico_alpha.is_empty() {
+            if let Err(e) = salvar_historico_alpha("alpha.csv", &self.historico_alpha) {
+                eprintln!("Erro ao salvar o histórico do alpha: {}", e);
+            }
+        }
+
+        self.finalizar(self.passos_executados);
+
+        self.relatar_violacoes("no fim");
+
+        if let Err(e) = self.relatorio_formigas("formigas.csv") {
+            eprintln!("Erro ao salvar as estatísticas das formigas: {}", e);
+        }
+
+        if let Err(e) = self.relatorio_anomalias("anomalias.csv") {
+            eprintln!("Erro ao salvar os outliers: {}", e);
+        }
+
+        println!("Fim do programa");
+        let mut contador_img = self.contador_img;
+        if let Err(e) = self.gerar_imagem_com_log("Cenario-final.png", &mut contador_img) {
+            eprintln!("Erro ao gerar a imagem final: {}", e);
+        }
+        self.contador_img = contador_img;
+        self.concluido = true;
+    }
+
+    /// Média, mínimo e máximo do alpha das formigas depois de `passos` passos
+    fn resumo_alpha(&self, passos: i64) -> (i64, f64, f64, f64) {
+        let alphas = self.formigas.iter().map(|f| f.alpha);
+        let media = alphas.clone().sum::<f64>() / self.formigas.len().max(1) as f64;
+        let minimo = alphas.clone().fold(f64::INFINITY, f64::min);
+        let maximo = alphas.fold(f64::NEG_INFINITY, f64::max);
+
+        (passos, media, minimo, maximo)
+    }
+
+    /// Executa um lote de passos no modo de atualização configurado. `inicio` é o número
+    /// de passos já executados antes do lote. Com o feromônio ligado o lote é dividido em
+    /// trechos de `periodo_feromonio` passos, e o campo é atualizado depois de cada um.
+    fn executar_lote(&mut self, passos: i64, inicio: i64) -> i64 {
+        let trecho = match self.mapa.feromonio() {
+            Some(_) => self.configuracao.periodo_feromonio.max(1),
+            None => passos,
+        };
+
+        let mut executados: i64 = 0;
+        while executados < passos {
+            let tamanho = trecho.min(passos - executados);
+            let feitos = match self.configuracao.modo_atualizacao {
+                ModoAtualizacao::Assincrono => {
+                    self.executar_lote_assincrono(tamanho, inicio + executados)
+                }
+                ModoAtualizacao::Sincrono => {
+                    self.executar_lote_sincrono(tamanho, inicio + executados)
+                }
+            };
+            if feitos == 0 {
+                break;
+            }
+            executados += feitos;
+
+            if let Some(campo) = self.mapa.feromonio() {
+                campo.atualizar(
+                    self.configuracao.evaporacao_feromonio,
+                    self.configuracao.difusao_feromonio,
+                );
+            }
+        }
+
+        executados
+    }
+
+    /// Executa um lote de passos no conjunto fixo de threads de trabalho, do tamanho do
+    /// número de CPUs, criado no primeiro lote e mantido até o fim da execução. Retorna a
+    /// soma dos passos executados por cada thread.
+    fn executar_lote_assincrono(&mut self, passos: i64, inicio: i64) -> i64 {
+        let trabalhadores = self.trabalhadores.get_or_insert_with(|| {
+            let num_threads = thread::available_parallelism().map_or(1, |n| n.get());
+            Trabalhadores::new(
+                num_threads,
+                Arc::clone(&self.mapa),
+                Arc::clone(&self.configuracao),
+            )
+        });
+
+        trabalhadores.executar_lote(&mut self.formigas, passos, inicio)
+    }
+
+    /// Executa um lote em rodadas síncronas de um passo por formiga. Todas as propostas
+    /// de uma rodada são feitas sobre o mesmo estado do mapa e depois aplicadas na ordem
+    /// das formigas, de modo que nos conflitos (mesmo grão ou mesma célula livre) vence
+    /// sempre a formiga de menor índice.
+    ///
+    /// Executa exatamente `passos` passos: se eles não forem múltiplo do número de
+    /// formigas, só as primeiras formigas agem na última rodada.
+    ///
+    /// As rodadas são curtas demais para compensar a sincronização entre threads, então
+    /// este modo roda na thread principal.
+    fn executar_lote_sincrono(&mut self, passos: i64, inicio: i64) -> i64 {
+        if self.formigas.is_empty() {
+            return 0;
+        }
+
+        let num_formigas = self.formigas.len() as i64;
+        let mut executados = 0;
+
+        while executados < passos {
+            // Quando faltam menos passos que formigas, a última rodada é só das primeiras
+            let ativas = (passos - executados).min(num_formigas) as usize;
+            let passo_atual = inicio + executados;
+            let propostas: Vec<Proposta> = self.formigas[..ativas]
+                .iter()
+                .map(|formiga| formiga.propor(&self.mapa, &self.configuracao, passo_atual))
+                .collect();
+
+            for (formiga, proposta) in self.formigas.iter_mut().zip(propostas) {
+                formiga.aplicar(proposta, &self.mapa, &self.configuracao);
+            }
+            executados += ativas as i64;
+        }
+
+        executados
+    }
+
+    /// Fase de finalização: as formigas que ainda seguram grãos andam sem pegar novos
+    /// até largá-los, para que o mapa final contenha todos os dados
+    fn finalizar(&mut self, passo_atual: i64) {
+        let passos = match self.configuracao.passos_finalizacao {
+            Some(passos) => passos,
+            None => return,
+        };
+
+        for formiga in &mut self.formigas {
+            formiga.finalizar(&self.mapa, &self.configuracao, passos, passo_atual);
+        }
+    }
+
+    /// Mostra as restrições da grade desrespeitadas no momento
+    fn relatar_violacoes(&self, momento: &str) {
+        let posicoes: Vec<Ponto> = self.formigas.iter().map(|f| f.posicao).collect();
+        let violacoes = self.mapa.violacoes(&posicoes);
+        if violacoes.is_empty() {
+            return;
+        }
+
+        println!(
+            "{} violações das restrições da grade {}:",
+            violacoes.len(),
+            momento
+        );
+        for violacao in violacoes.iter().take(10) {
+            println!("  {}", violacao);
+        }
+        if violacoes.len() > 10 {
+            println!("  ...");
+        }
+    }
+
+    /// Pontua os grãos do mapa como outliers, salva a lista ordenada e mostra os primeiros.
+    /// Os `destacar_anomalias` primeiros são marcados nas imagens geradas depois. A
+    /// pontuação usa o alpha médio das formigas no fim (que já reflete o alpha adaptativo e
+    /// os das populações), mas o raio e o modelo da configuração, e não os das populações.
+    fn relatorio_anomalias(&mut self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let perfil = Perfil::default();
+        let (passo, alpha, _, _) = self.resumo_alpha(self.passos_executados);
+        let parametros = self.configuracao.parametros_no_passo(passo, alpha, &perfil);
+        let anomalias = pontuar_anomalias(&self.mapa, &self.configuracao, &parametros);
+        salvar_anomalias(path, &anomalias)?;
+
+        println!("Prováveis outliers (lista completa em {}):", path);
+        for anomalia in anomalias.iter().take(5) {
+            println!(
+                "  ({}, {}), grupo {}: {} vizinhos, similaridade {:.4}, pontuação {:.4}",
+                anomalia.grao.posicao.x,
+                anomalia.grao.posicao.y,
+                anomalia.grao.grupo,
+                anomalia.vizinhos,
+                anomalia.similaridade,
+                anomalia.pontuacao
+            );
+        }
+
+        self.destaques = anomalias
+            .iter()
+            .take(self.configuracao.destacar_anomalias)
+            .map(|anomalia| anomalia.grao.posicao)
+            .collect();
+        Ok(())
+    }
+
+    /// Salva as estatísticas de cada formiga num CSV separado por `;` e mostra o resumo de
+    /// cada população
+    fn relatorio_formigas(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut conteudo = String::from(
+            "formiga;populacao;alpha;velocidade;raio;modelo;passos;pegadas;largadas;estagnacoes\n",
+        );
+        // Por população: formigas, soma dos alphas, pegadas e largadas
+        let mut resumo: Vec<(usize, f64, u64, u64)> = vec![];
+        let mut estagnacoes: u64 = 0;
+
+        for formiga in &self.formigas {
+            let perfil = &formiga.perfil;
+            let modelo = perfil
+                .modelo
+                .as_ref()
+                .unwrap_or(&self.configuracao.modelo)
+                .nome();
+            conteudo.push_str(&format!(
+                "{};{};{};{};{};{};{};{};{};{}\n",
+                formiga.id,
+                perfil.populacao,
+                formiga.alpha,
+                perfil.velocidade.unwrap_or(1),
+                perfil
+                    .raio_vizinhanca
+                    .unwrap_or(self.configuracao.raio_vizinhanca),
+                modelo,
+                formiga.passos,
+                formiga.pegadas,
+                formiga.largadas,
+                formiga.estagnacoes
+            ));
+            estagnacoes += formiga.estagnacoes;
+
+            if resumo.len() <= perfil.populacao {
+                resumo.resize(perfil.populacao + 1, (0, 0.0, 0, 0));
+            }
+            let linha = &mut resumo[perfil.populacao];
+            linha.0 += 1;
+            linha.1 += formiga.alpha;
+            linha.2 += formiga.pegadas;
+            linha.3 += formiga.largadas;
+        }
+
+        for (populacao, (formigas, alphas, pegadas, largadas)) in resumo.iter().enumerate() {
+            if *formigas > 0 {
+                println!(
+                    "População {}: {} formigas, alpha médio {:.4}, {} pegadas, {} largadas",
+                    populacao,
+                    formigas,
+                    alphas / *formigas as f64,
+                    pegadas,
+                    largadas
+                );
+            }
+        }
+
+        if self.configuracao.regra_estagnacao != RegraEstagnacao::Nenhuma {
+            println!(
+                "Regra de estagnação `{}` agiu {} vezes",
+                self.configuracao.regra_estagnacao, estagnacoes
+            );
+        }
+
+        std::fs::write(path, conteudo)?;
+        println!("Estatísticas das formigas salvas em {}", path);
+        Ok(())
+    }
+
+    /// Função auxiliar para gerar a imagem e fazer o log
+    fn gerar_imagem_com_log(
+        &self,
+        path: &str,
+        contador_img: &mut i64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        println!("Gerando imagem: {}", path);
+        self.gerar_imagem(path, (800, 640))?;
+        *contador_img += 1;
+        println!("Imagem {} gerada com sucesso!", path);
+        Ok(())
+    }
+
+    /// Posição de uma célula na imagem. Na grade quadrada é o canto da célula; na
+    /// hexagonal é o centro do hexágono, com as linhas ímpares deslocadas meia célula.
+    fn posicao_na_imagem(&self, posicao: &Ponto, escala: (f64, f64)) -> (i32, i32) {
+        let (x, y) = match self.grade.topologia {
+            Topologia::Quadrada => (posicao.x as f64, posicao.y as f64),
+            Topologia::Hexagonal => (
+                posicao.x as f64 + 0.5 + 0.5 * (posicao.y & 1) as f64,
+                posicao.y as f64 + 0.5,
+            ),
+        };
+
+        ((x * escala.0).round() as i32, (y * escala.1).round() as i32)
+    }
+
+    pub fn gerar_imagem(
+        &self,
+        path: &str,
+        image_dimensions: (u32, u32),
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (img_width, img_height) = image_dimensions;
+        let base_dimensions: (f64, f64) = match self.grade.topologia {
+            Topologia::Quadrada => (self.grade.largura as f64, self.grade.altura as f64),
+            // As linhas ímpares da grade hexagonal avançam meia célula para a direita
+            Topologia::Hexagonal => (self.grade.largura as f64 + 0.5, self.grade.altura as f64),
+        };
+
+        // Cria uma imagem com fundo preto
+        let mut img = ImageBuffer::from_pixel(img_width, img_height, Rgb([0u8, 0u8, 0u8]));
+
+        // Escalar o tamanho da imagem real em relação à base_dimensions fornecida
+        let scale_x = img_width as f64 / base_dimensions.0;
+        let scale_y = img_height as f64 / base_dimensions.1;
+
+        // Reduz o tamanho dos grãos e das formigas pela metade, com pelo menos um pixel nas
+        // grades maiores que a imagem
+        let tamanho_grao = (((1.0 * scale_x / 1.0).round() as i32) / 2).max(1); // Reduzido pela metade
+        let raio_formiga = ((((1.0 * scale_x / 1.0).round() as i32) / 2) / 2).max(1); // Raio também reduzido pela metade
+
+        // Desenha o feromônio por baixo de tudo, em verde proporcional ao máximo
+        if let Some(campo) = self
+            .mapa
+            .feromonio()
+            .filter(|_| self.configuracao.desenhar_feromonio)
+        {
+            let maximo = campo.maximo();
+            for y in 0..self.grade.altura {
+                for x in 0..self.grade.largura {
+                    let celula = Ponto { x, y };
+                    let valor = campo.valor(&celula);
+                    if maximo <= 0.0 || valor <= 0.0 {
+                        continue;
+                    }
+
+                    let cor = Rgb([0u8, (valor / maximo * 160.0).round() as u8, 0u8]);
+                    let (x_px, y_px) = self.posicao_na_imagem(&celula, (scale_x, scale_y));
+                    match self.grade.topologia {
+                        Topologia::Quadrada => {
+                            let rect = Rect::at(x_px, y_px).of_size(
+                                scale_x.ceil().max(1.0) as u32,
+                                scale_y.ceil().max(1.0) as u32,
+                            );
+                            imageproc::drawing::draw_filled_rect_mut(&mut img, rect, cor);
+                        }
+                        Topologia::Hexagonal => {
+                            desenhar_hexagono(&mut img, x_px, y_px, (scale_x, scale_y), cor);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Desenha os grãos. No modelo de pilhas cada célula é desenhada uma vez, com a cor
+        // do grão do topo e o tamanho crescendo com a altura da pilha.
+        let pilhas = self.mapa.pilhas();
+        let graos: Vec<(&Grao, usize)> = if self.mapa.usa_pilhas() {
+            pilhas
+                .iter()
+                .filter_map(|pilha| pilha.last().map(|topo| (topo, pilha.len())))
+                .collect()
+        } else {
+            pilhas.iter().flatten().map(|grao| (grao, 1)).collect()
+        };
+        let maior_pilha = graos.iter().map(|(_, altura)| *altura).max().unwrap_or(1);
+        println!(
+            "Numero de grãos: {}",
+            graos.iter().map(|(_, altura)| altura).sum::<usize>()
+        );
+
+        for (grao, altura) in graos {
+            // Fração da célula ocupada por uma pilha: metade para um grão, a célula inteira
+            // para a maior pilha
+            let fracao = if maior_pilha > 1 {
+                0.5 + 0.5 * ((altura - 1) as f64 / (maior_pilha - 1) as f64).sqrt()
+            } else {
+                0.5
+            };
+            let pilha = self.mapa.usa_pilhas();
+
+            // Verifica se já existe uma cor associada ao grupo de dados desse grão
+            let cor = self
+                .cores_por_grupo
+                .get(&format!("{:?}", grao.grupo))
+                .expect("Cor não encontrada para o grupo de grãos");
+
+            // Ajustar as coordenadas dos grãos para o tamanho da imagem
+            let (x_px, y_px) = self.posicao_na_imagem(&grao.posicao, (scale_x, scale_y));
+
+            match self.grade.topologia {
+                Topologia::Quadrada => {
+                    // Desenha o quadrado (retângulo) representando o grão, com a cor do grupo
+                    let lado = if pilha {
+                        ((scale_x * fracao).round() as i32).max(1)
+                    } else {
+                        tamanho_grao
+                    };
+                    let rect = Rect::at(x_px, y_px).of_size(lado as u32, lado as u32);
+                    imageproc::drawing::draw_filled_rect_mut(&mut img, rect, *cor);
+                }
+                Topologia::Hexagonal => {
+                    // Desenha a célula hexagonal inteira, para que os aglomerados apareçam
+                    // como regiões contínuas; as pilhas menores ocupam só parte dela
+                    let escala = if pilha {
+                        (scale_x * fracao, scale_y * fracao)
+                    } else {
+                        (scale_x, scale_y)
+                    };
+                    desenhar_hexagono(&mut img, x_px, y_px, escala, *cor);
+                }
+            }
+        }
+
+        // Circula os outliers destacados, em branco, por cima dos grãos
+        const BRANCO: Rgb<u8> = Rgb([255u8, 255u8, 255u8]);
+        let raio_destaque = (scale_x.max(scale_y).round() as i32).max(2);
+        for posicao in &self.destaques {
+            let (x_px, y_px) = self.posicao_na_imagem(posicao, (scale_x, scale_y));
+            let centro = match self.grade.topologia {
+                Topologia::Quadrada => (x_px + tamanho_grao / 2, y_px + tamanho_grao / 2),
+                Topologia::Hexagonal => (x_px, y_px),
+            };
+            draw_hollow_circle_mut(&mut img, centro, raio_destaque, BRANCO);
+        }
+
+        // Desenha as formigas
+        const VERMELHO: Rgb<u8> = Rgb([255u8, 0u8, 0u8]);
+        const AMARELO: Rgb<u8> = Rgb([255u8, 255u8, 0u8]);
+
+        for formiga in self.formigas.iter() {
+            let pos = formiga.posicao;
+
+            // Ajustar as coordenadas das formigas para o tamanho da imagem
+            let (x_px, y_px) = self.posicao_na_imagem(&pos, (scale_x, scale_y));
+
+            let cor_formiga = if formiga.segurando_objeto.is_some() {
+                AMARELO
+            } else {
+                VERMELHO
+            };
+
+            // Desenha um círculo representando a formiga, com a cor apropriada
+            draw_filled_circle_mut(&mut img, (x_px, y_px), raio_formiga, cor_formiga);
+        }
+
+        // Salva a imagem em um arquivo
+        match img.save(path) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!("Erro ao salvar a imagem: {}", e).into()),
+        }
+    }
+}
+
+impl AlgoritmoEnxame for Cenario {
+    fn nome(&self) -> &'static str {
+        "formigas"
+    }
+
+    fn inicializar(&mut self) {
+        // Registra as opções da execução antes de começar
+        if let Err(e) = std::fs::write("manifesto.txt", self.configuracao.manifesto()) {
+            eprintln!("Erro ao salvar o manifesto: {}", e);
+        }
+        self.passos_executados = 0;
+        self.gerou_percentuais = [false; 2];
+        self.concluido = false;
+
+        self.relatar_violacoes("no início");
+
+        // Evolução do alpha adaptativo: (passos, média, mínimo, máximo)
+        self.historico_alpha.clear();
+        if self.configuracao.alpha_adaptativo {
+            let resumo = self.resumo_alpha(self.passos_executados);
+            self.historico_alpha.push(resumo);
+        }
+    }
+
+    /// Executa um lote de passos das formigas; depois do último, conclui a execução
+    fn passo(&mut self) {
+        let restante = self.numero_interacoes - self.passos_executados;
+        if restante <= 0 {
+            self.concluir();
+            return;
+        }
+
+        // Calcula o percentual restante
+        let percentual_restante = restante as f64 / self.numero_interacoes as f64;
+
+        // Gera imagens em diferentes estágios de progresso
+        for (i, &percentual) in PORCENTAGENS_IMAGENS.iter().enumerate() {
+            if !self.gerou_percentuais[i] && percentual_restante <= percentual {
+                let mut contador_img = self.contador_img;
+                if let Err(e) = self.gerar_imagem_com_log(
+                    &format!("Cenario-{}.png", contador_img),
+                    &mut contador_img,
+                ) {
+                    eprintln!("Erro ao gerar a imagem: {}", e);
+                }
+                self.contador_img = contador_img;
+                self.gerou_percentuais[i] = true;
+            }
+        }
+
+        let lote = restante.min(PASSOS_POR_LOTE);
+        self.passos_executados += self.executar_lote(lote, self.passos_executados);
+
+        if self.configuracao.alpha_adaptativo {
+            let resumo = self.resumo_alpha(self.passos_executados);
+            println!(
+                "Passo {}: alpha médio {:.4} (mín. {:.4}, máx. {:.4})",
+                resumo.0, resumo.1, resumo.2, resumo.3
+            );
+            self.historico_alpha.push(resumo);
+        }
+
+        if self.passos_executados >= self.numero_interacoes {
+            self.concluir();
+        }
+    }
+
+    fn terminou(&self) -> bool {
+        self.concluido
+    }
+
+    /// Os agrupamentos são as regiões conectadas de células com grãos
+    fn agrupamentos(&self) -> (Vec<Grao>, Vec<usize>) {
+        let graos = self.mapa.graos();
+        let rotulos = rotulos_da_grade(&graos, &self.grade);
+        (graos, rotulos)
+    }
+}
+
+/// Ocupa as células das formigas, sorteando outra célula livre para as que nasceram numa
+/// célula já ocupada. Se não houver células para todas, as que sobram ficam onde estão e
+/// aparecem no relatório de violações.
+fn posicionar_formigas(formigas: &mut [Formiga], mapa: &Mapa) {
+    let mut rng = rand::thread_rng();
+    let tentativas = mapa.grade().num_celulas() * 4;
+
+    for formiga in formigas {
+        if mapa.ocupar(&formiga.posicao) {
+            continue;
+        }
+
+        for _ in 0..tentativas {
+            let celula = mapa.grade().ponto_aleatorio(&mut rng);
+            if mapa.ocupar(&celula) {
+                formiga.posicao = celula;
+                break;
+            }
+        }
+    }
+}
+
+/// Salva a evolução do alpha adaptativo num CSV separado por `;`
+fn salvar_historico_alpha(
+    path: &str,
+    historico: &[(i64, f64, f64, f64)],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut conteudo = String::from("passo;alpha_medio;alpha_min;alpha_max\n");
+    for (passo, media, minimo, maximo) in historico {
+        conteudo.push_str(&format!("{};{};{};{}\n", passo, media, minimo, maximo));
+    }
+
+    std::fs::write(path, conteudo)?;
+    println!("Histórico do alpha salvo em {}", path);
+    Ok(())
+}
+
+/// Desenha o hexágono de `hexagono`. Quando a célula tem menos de um pixel de altura os
+/// vértices se confundem e o polígono não pode ser desenhado; ela vira um retângulo de
+/// pelo menos um pixel.
+fn desenhar_hexagono(
+    img: &mut ImageBuffer<Rgb<u8>, Vec<u8>>,
+    x_px: i32,
+    y_px: i32,
+    escala: (f64, f64),
+    cor: Rgb<u8>,
+) {
+    let vertices = hexagono(x_px, y_px, escala);
+    if vertices.first() != vertices.last() {
+        draw_polygon_mut(img, &vertices, cor);
+        return;
+    }
+
+    let largura = escala.0.round().max(1.0) as u32;
+    let altura = escala.1.round().max(1.0) as u32;
+    let rect =
+        Rect::at(x_px - largura as i32 / 2, y_px - altura as i32 / 2).of_size(largura, altura);
+    imageproc::drawing::draw_filled_rect_mut(img, rect, cor);
+}
+
+/// Vértices de um hexágono de pontas para cima que ocupa uma célula de tamanho `escala`
+fn hexagono(x_px: i32, y_px: i32, escala: (f64, f64)) -> Vec<Point<i32>> {
+    // Na grade hexagonal a largura da célula é √3·r e a distância entre linhas é 1,5·r
+    let raio_x = escala.0 / 3f64.sqrt();
+    let raio_y = escala.1 / 1.5;
+
+    (0..6)
+        .map(|i| {
+            let angulo = (60.0 * i as f64 + 30.0).to_radians();
+            Point::new(
+                x_px + (raio_x * angulo.cos()).round() as i32,
+                y_px + (raio_y * angulo.sin()).round() as i32,
+            )
+        })
+        .collect()
+}
+
+/// Gera uma cor aleatória que não seja similar a vermelho ou amarelo
+fn gerar_cor_aleatoria() -> Rgb<u8> {
+    let mut rng = rand::thread_rng();
+
+    loop {
+        // Gera uma cor aleatória
+        let cor = Rgb([rng.gen::<u8>(), rng.gen::<u8>(), rng.gen::<u8>()]);
+
+        // Verifica se a cor gerada é similar a vermelho ou amarelo
+        // Critérios simples para evitar vermelho/amarelo:
+        // - Evitar cores onde o valor de R (vermelho) é dominante
+        // - Evitar valores altos simultâneos de R e G (que formariam amarelo)
+        if !cor_semelhante_vermelho_ou_amarelo(&cor) {
+            return cor;
+        }
+    }
+}
+
+/// Verifica se uma cor se assemelha a vermelho ou amarelo
+fn cor_semelhante_vermelho_ou_amarelo(cor: &Rgb<u8>) -> bool {
+    let Rgb([r, g, b]) = cor;
+
+    // Verifica se o vermelho (R) é dominante (evita vermelho)
+    if *r > 200 && *g < 100 && *b < 100 {
+        return true;
+    }
+
+    // Verifica se o vermelho (R) e o verde (G) são altos ao mesmo tempo (evita amarelo)
+    if *r > 200 && *g > 200 && *b < 100 {
+        return true;
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::formiga::gerar_formigas;
+    use crate::simulacao::grade::Fronteira;
+    use crate::simulacao::grao::gerar_graos;
+
+    /// Gera a imagem de um cenário com a grade dada, com formigas, grãos e um destaque
+    fn renderizar(largura: i32, altura: i32, topologia: Topologia, pilhas: bool) {
+        let grade = Grade::new(largura, altura, Fronteira::Toroidal, topologia);
+        let configuracao = Configuracao {
+            grade,
+            pilhas,
+            ..Configuracao::default()
+        };
+        let mut graos = gerar_graos(200, &grade, vec![0.0], 1);
+        // Uma pilha de três grãos, no modelo de pilhas
+        for grao in &mut graos[..3] {
+            grao.posicao = Ponto { x: 0, y: 0 };
+        }
+        let mut cenario = Cenario::new(gerar_formigas(20, &configuracao), graos, configuracao);
+        cenario.destaques = vec![Ponto { x: 0, y: 0 }];
+
+        let caminho = std::env::temp_dir().join(format!(
+            "cenario_{}_{}x{}_{:?}_{}.png",
+            std::process::id(),
+            largura,
+            altura,
+            topologia,
+            pilhas
+        ));
+        cenario
+            .gerar_imagem(caminho.to_str().unwrap(), (800, 640))
+            .unwrap();
+        std::fs::remove_file(caminho).unwrap();
+    }
+
+    #[test]
+    fn grade_quadrada_maior_que_a_imagem() {
+        for pilhas in [false, true] {
+            renderizar(1000, 1000, Topologia::Quadrada, pilhas);
+        }
+    }
+
+    #[test]
+    fn grade_hexagonal_alta() {
+        for pilhas in [false, true] {
+            renderizar(64, 500, Topologia::Hexagonal, pilhas);
+            renderizar(64, 214, Topologia::Hexagonal, pilhas);
+        }
+    }
+}
@@ -0,0 +1,364 @@
+use super::configuracao::Configuracao;
+use super::constantes::ITERACOES_KMEDIAS;
+use super::densidade::distancia_entre_par_de_dados;
+use super::enxame::{grupos_padrao, AlgoritmoEnxame};
+use super::grao::Grao;
+use rand::Rng;
+
+// Algoritmos clássicos usados como referência na comparação. Não são de enxame, mas seguem
+// a mesma interface para serem executados e avaliados da mesma forma.
+
+/// k-means com a inicialização do k-means++, sobre os dados dos grãos
+#[derive(Clone, Debug)]
+pub struct KMedias {
+    graos: Vec<Grao>,
+    grupos: usize,
+    /// Centroides iniciais; vazio, eles são sorteados pelo k-means++
+    sementes: Vec<Vec<f64>>,
+    centroides: Vec<Vec<f64>>,
+    rotulos: Vec<usize>,
+    iteracao: usize,
+    mudou: bool,
+}
+
+impl KMedias {
+    pub fn new(graos: Vec<Grao>, configuracao: &Configuracao) -> KMedias {
+        let grupos = configuracao
+            .grupos
+            .unwrap_or_else(|| grupos_padrao(graos.len()))
+            .clamp(1, graos.len().max(1));
+
+        KMedias {
+            graos,
+            grupos,
+            sementes: vec![],
+            centroides: vec![],
+            rotulos: vec![],
+            iteracao: 0,
+            mudou: true,
+        }
+    }
+
+    /// k-means que começa dos centroides dados em vez de sorteá-los
+    pub fn com_sementes(graos: Vec<Grao>, sementes: Vec<Vec<f64>>) -> KMedias {
+        KMedias {
+            graos,
+            grupos: sementes.len(),
+            sementes,
+            centroides: vec![],
+            rotulos: vec![],
+            iteracao: 0,
+            mudou: true,
+        }
+    }
+
+    /// Índice do centroide mais próximo de cada grão
+    fn rotular(&self) -> Vec<usize> {
+        self.graos
+            .iter()
+            .map(|grao| mais_proximo(&grao.dados, &self.centroides).0)
+            .collect()
+    }
+}
+
+/// Índice e distância do centroide mais próximo dos dados
+fn mais_proximo(dados: &[f64], centroides: &[Vec<f64>]) -> (usize, f64) {
+    centroides
+        .iter()
+        .map(|centroide| distancia_entre_par_de_dados(dados, centroide))
+        .enumerate()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((0, 0.0))
+}
+
+impl AlgoritmoEnxame for KMedias {
+    fn nome(&self) -> &'static str {
+        "k-means++"
+    }
+
+    /// O primeiro centroide é um grão sorteado; cada um dos seguintes é sorteado com
+    /// probabilidade proporcional ao quadrado da distância ao centroide mais próximo.
+    /// Com sementes, elas são os centroides iniciais.
+    fn inicializar(&mut self) {
+        let mut rng = rand::thread_rng();
+        self.centroides = self.sementes.clone();
+        if self.centroides.is_empty() && !self.graos.is_empty() {
+            let primeiro = rng.gen_range(0..self.graos.len());
+            self.centroides.push(self.graos[primeiro].dados.clone());
+        }
+
+        while self.centroides.len() < self.grupos {
+            let pesos: Vec<f64> = self
+                .graos
+                .iter()
+                .map(|grao| mais_proximo(&grao.dados, &self.centroides).1.powi(2))
+                .collect();
+            let total: f64 = pesos.iter().sum();
+
+            // Com todos os grãos sobre centroides não há mais grãos distintos a escolher
+            if total <= 0.0 {
+                break;
+            }
+
+            let mut sorteio = rng.gen::<f64>() * total;
+            let escolhido = pesos
+                .iter()
+                .position(|peso| {
+                    sorteio -= peso;
+                    sorteio <= 0.0
+                })
+                .unwrap_or(pesos.len() - 1);
+            self.centroides.push(self.graos[escolhido].dados.clone());
+        }
+
+        self.rotulos = self.rotular();
+        self.iteracao = 0;
+        self.mudou = true;
+    }
+
+    /// Uma iteração de Lloyd: move os centroides para a média dos seus grãos e rotula de
+    /// novo. Um agrupamento que ficou vazio mantém o centroide.
+    fn passo(&mut self) {
+        let dimensao = self.graos.first().map_or(0, |grao| grao.dados.len());
+        let mut somas = vec![vec![0.0; dimensao]; self.centroides.len()];
+        let mut quantidades = vec![0usize; self.centroides.len()];
+        for (grao, &rotulo) in self.graos.iter().zip(&self.rotulos) {
+            for (soma, valor) in somas[rotulo].iter_mut().zip(&grao.dados) {
+                *soma += valor;
+            }
+            quantidades[rotulo] += 1;
+        }
+        for ((centroide, soma), quantidade) in
+            self.centroides.iter_mut().zip(somas).zip(quantidades)
+        {
+            if quantidade > 0 {
+                *centroide = soma.iter().map(|s| s / quantidade as f64).collect();
+            }
+        }
+
+        let rotulos = self.rotular();
+        self.mudou = rotulos != self.rotulos;
+        self.rotulos = rotulos;
+        self.iteracao += 1;
+    }
+
+    fn terminou(&self) -> bool {
+        !self.mudou || self.iteracao >= ITERACOES_KMEDIAS
+    }
+
+    fn agrupamentos(&self) -> (Vec<Grao>, Vec<usize>) {
+        (self.graos.clone(), self.rotulos.clone())
+    }
+}
+
+/// DBSCAN sobre os dados dos grãos. Cada grão de ruído conta como um agrupamento sozinho.
+#[derive(Clone, Debug)]
+pub struct Dbscan {
+    graos: Vec<Grao>,
+    /// Raio da vizinhança; `None` estima pela distância média ao `minimo`-ésimo vizinho
+    raio: Option<f64>,
+    /// Vizinhos dentro do raio, contando o próprio grão, para ele ser um núcleo
+    minimo: usize,
+    rotulos: Option<Vec<usize>>,
+}
+
+impl Dbscan {
+    pub fn new(graos: Vec<Grao>, configuracao: &Configuracao) -> Dbscan {
+        Dbscan {
+            graos,
+            raio: configuracao.raio_dbscan,
+            minimo: configuracao.minimo_dbscan.max(1),
+            rotulos: None,
+        }
+    }
+
+    /// Média, entre os grãos, da distância ao `minimo`-ésimo vizinho mais próximo
+    fn estimar_raio(&self) -> f64 {
+        let n = self.graos.len();
+        if n < 2 {
+            return 0.0;
+        }
+
+        let posicao = self.minimo.min(n - 1).max(1) - 1;
+        let soma: f64 = self
+            .graos
+            .iter()
+            .enumerate()
+            .map(|(i, grao)| {
+                let mut distancias: Vec<f64> = self
+                    .graos
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, outro)| distancia_entre_par_de_dados(&grao.dados, &outro.dados))
+                    .collect();
+                distancias.sort_by(f64::total_cmp);
+                distancias[posicao]
+            })
+            .sum();
+
+        soma / n as f64
+    }
+
+    fn vizinhos(&self, indice: usize, raio: f64) -> Vec<usize> {
+        (0..self.graos.len())
+            .filter(|&outro| {
+                distancia_entre_par_de_dados(&self.graos[indice].dados, &self.graos[outro].dados)
+                    <= raio
+            })
+            .collect()
+    }
+}
+
+impl AlgoritmoEnxame for Dbscan {
+    fn nome(&self) -> &'static str {
+        "DBSCAN"
+    }
+
+    fn inicializar(&mut self) {
+        self.rotulos = None;
+    }
+
+    /// Agrupa todos os grãos de uma vez, expandindo cada núcleo ainda sem agrupamento
+    fn passo(&mut self) {
+        let raio = self.raio.unwrap_or_else(|| self.estimar_raio());
+        let n = self.graos.len();
+        let mut rotulos: Vec<Option<usize>> = vec![None; n];
+        let mut agrupamentos = 0;
+
+        for inicio in 0..n {
+            if rotulos[inicio].is_some() {
+                continue;
+            }
+            let vizinhos = self.vizinhos(inicio, raio);
+            if vizinhos.len() < self.minimo {
+                continue;
+            }
+
+            rotulos[inicio] = Some(agrupamentos);
+            let mut fila = vizinhos;
+            while let Some(grao) = fila.pop() {
+                if rotulos[grao].is_some() {
+                    continue;
+                }
+                rotulos[grao] = Some(agrupamentos);
+
+                let vizinhos = self.vizinhos(grao, raio);
+                if vizinhos.len() >= self.minimo {
+                    fila.extend(vizinhos.into_iter().filter(|v| rotulos[*v].is_none()));
+                }
+            }
+            agrupamentos += 1;
+        }
+
+        let ruidos = rotulos.iter().filter(|rotulo| rotulo.is_none()).count();
+        println!(
+            "DBSCAN: raio {:.4}, {} agrupamentos e {} grãos de ruído",
+            raio, agrupamentos, ruidos
+        );
+
+        let mut proximo = agrupamentos;
+        self.rotulos = Some(
+            rotulos
+                .into_iter()
+                .map(|rotulo| {
+                    rotulo.unwrap_or_else(|| {
+                        proximo += 1;
+                        proximo - 1
+                    })
+                })
+                .collect(),
+        );
+    }
+
+    fn terminou(&self) -> bool {
+        self.rotulos.is_some()
+    }
+
+    fn agrupamentos(&self) -> (Vec<Grao>, Vec<usize>) {
+        (
+            self.graos.clone(),
+            self.rotulos
+                .clone()
+                .unwrap_or_else(|| vec![0; self.graos.len()]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::avaliacao::avaliar;
+    use crate::simulacao::enxame::executar;
+    use crate::simulacao::outros::Ponto;
+
+    fn grao(x: f64, y: f64, grupo: i32) -> Grao {
+        Grao::new(Ponto { x: 0, y: 0 }, vec![x, y], grupo)
+    }
+
+    /// Duas nuvens de 15 grãos, em torno de (0, 0) e de (50, 50)
+    fn duas_nuvens() -> Vec<Grao> {
+        (0..30)
+            .map(|i| {
+                let grupo = i / 15;
+                let base = 50.0 * grupo as f64;
+                grao(
+                    base + (i % 3) as f64 * 0.3,
+                    base + (i % 5) as f64 * 0.3,
+                    grupo,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn kmedias_separa_duas_nuvens() {
+        let configuracao = Configuracao {
+            grupos: Some(2),
+            ..Configuracao::default()
+        };
+        let mut kmedias = KMedias::new(duas_nuvens(), &configuracao);
+
+        let avaliacao = executar(&mut kmedias);
+        assert_eq!(avaliacao.agrupamentos, 2);
+        assert_eq!(avaliacao.pureza, 1.0);
+        assert_eq!(avaliacao.indice_rand, 1.0);
+    }
+
+    #[test]
+    fn kmedias_com_sementes_parte_delas() {
+        let graos = duas_nuvens();
+        let mut kmedias = KMedias::com_sementes(graos, vec![vec![50.0, 50.0], vec![0.0, 0.0]]);
+        executar(&mut kmedias);
+
+        // Os rótulos seguem a ordem das sementes
+        let (graos, rotulos) = kmedias.agrupamentos();
+        for (grao, rotulo) in graos.iter().zip(rotulos) {
+            assert_eq!(rotulo, 1 - grao.grupo as usize);
+        }
+    }
+
+    #[test]
+    fn dbscan_marca_grao_isolado_como_ruido() {
+        let mut graos = duas_nuvens();
+        graos.push(grao(25.0, 25.0, 2));
+        let configuracao = Configuracao {
+            raio_dbscan: Some(1.0),
+            minimo_dbscan: 3,
+            ..Configuracao::default()
+        };
+        let mut dbscan = Dbscan::new(graos, &configuracao);
+        executar(&mut dbscan);
+
+        let (graos, rotulos) = dbscan.agrupamentos();
+        let isolado = rotulos[graos.len() - 1];
+        assert!(rotulos[..graos.len() - 1].iter().all(|r| *r != isolado));
+        assert!(rotulos[..15].iter().all(|r| *r == rotulos[0]));
+        assert!(rotulos[15..30].iter().all(|r| *r == rotulos[15]));
+        assert_ne!(rotulos[0], rotulos[15]);
+
+        let avaliacao = avaliar(&graos, &rotulos);
+        assert_eq!(avaliacao.agrupamentos, 3);
+        assert_eq!(avaliacao.pureza, 1.0);
+    }
+}
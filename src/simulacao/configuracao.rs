@@ -0,0 +1,620 @@
+use super::agenda::Agenda;
+use super::automatico::ParametrosAutomaticos;
+use super::constantes::{
+    ALPHA, ALTURA_GRADE, ATRACAO_FEROMONIO, BONUS_RESTRICAO, COGNITIVO_PARTICULAS,
+    DEPOSITO_FEROMONIO, DIFUSAO_FEROMONIO, EVAPORACAO_FEROMONIO, EXPOENTE_LEVY,
+    INCLINACAO_SIGMOIDE, INERCIA_PARTICULAS, ITERACOES_PARTICULAS, K1, K2, LARGURA_GRADE,
+    LIMITE_ESTAGNACAO, MINIMO_DBSCAN, NUM_FORMIGAS, NUM_PARTICULAS, PASSOS_FINALIZACAO,
+    PASSO_ALPHA, PENALIDADE_RESTRICAO, PERIODO_ALPHA, PERIODO_FEROMONIO, PERSISTENCIA,
+    RAIO_ESTAGNACAO, SIGMA_VIZINHANCA, SOCIAL_PARTICULAS, TAMANHO_VIZINHANCA, TAXA_FALHA_ALVO,
+    VELOCIDADE,
+};
+use super::densidade::FuncaoDensidade;
+use super::estagnacao::RegraEstagnacao;
+use super::formiga::Perfil;
+use super::grade::{FormatoVizinhanca, Fronteira, Grade, Topologia};
+use super::grao::Posicionamento;
+use super::modelos::{modelo_por_nome, Limiares, ModeloProbabilidade, Quadratico};
+use super::movimento::{movimento_por_nome, EstrategiaMovimento, Uniforme};
+use super::populacao::Populacao;
+use super::refinamento::Refinamento;
+use super::restricoes::Restricoes;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Write;
+use std::fs;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Forma como as formigas são atualizadas a cada passo
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ModoAtualizacao {
+    /// Cada formiga age assim que consegue o lock das células, sem ordem fixa
+    Assincrono,
+    /// Em cada rodada todas as formigas propõem movimento e ação sobre o mesmo estado do
+    /// mapa; as propostas são aplicadas juntas e, em conflito, vence a de menor índice
+    Sincrono,
+}
+
+impl FromStr for ModoAtualizacao {
+    type Err = String;
+
+    fn from_str(valor: &str) -> Result<Self, Self::Err> {
+        match valor {
+            "assincrono" => Ok(ModoAtualizacao::Assincrono),
+            "sincrono" => Ok(ModoAtualizacao::Sincrono),
+            _ => Err(format!("modo de atualização desconhecido `{}`", valor)),
+        }
+    }
+}
+
+impl fmt::Display for ModoAtualizacao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nome = match self {
+            ModoAtualizacao::Assincrono => "assincrono",
+            ModoAtualizacao::Sincrono => "sincrono",
+        };
+        write!(f, "{}", nome)
+    }
+}
+
+/// Algoritmo usado para agrupar os grãos
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Algoritmo {
+    /// Formigas que pegam e largam grãos na grade
+    Formigas,
+    /// Árvore construída pelas formigas do AntTree, cortada em agrupamentos
+    AntTree,
+    /// Enxame de partículas que busca os centroides dos agrupamentos
+    Particulas,
+    /// Referências clássicas para comparação
+    KMedias,
+    Dbscan,
+}
+
+impl Algoritmo {
+    /// Todos os algoritmos, na ordem em que a comparação os executa
+    pub const TODOS: [Algoritmo; 5] = [
+        Algoritmo::Formigas,
+        Algoritmo::AntTree,
+        Algoritmo::Particulas,
+        Algoritmo::KMedias,
+        Algoritmo::Dbscan,
+    ];
+}
+
+impl FromStr for Algoritmo {
+    type Err = String;
+
+    fn from_str(valor: &str) -> Result<Self, Self::Err> {
+        match valor {
+            "formigas" => Ok(Algoritmo::Formigas),
+            "anttree" => Ok(Algoritmo::AntTree),
+            "particulas" => Ok(Algoritmo::Particulas),
+            "kmedias" => Ok(Algoritmo::KMedias),
+            "dbscan" => Ok(Algoritmo::Dbscan),
+            _ => Err(format!("algoritmo desconhecido `{}`", valor)),
+        }
+    }
+}
+
+impl fmt::Display for Algoritmo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nome = match self {
+            Algoritmo::Formigas => "formigas",
+            Algoritmo::AntTree => "anttree",
+            Algoritmo::Particulas => "particulas",
+            Algoritmo::KMedias => "kmedias",
+            Algoritmo::Dbscan => "dbscan",
+        };
+        write!(f, "{}", nome)
+    }
+}
+
+/// Valores dos parâmetros que podem variar com o tempo ou de formiga para formiga, já
+/// resolvidos para um passo de uma formiga
+#[derive(Clone, Copy, Debug)]
+pub struct ParametrosPasso<'a> {
+    pub raio_vizinhanca: i32,
+    pub limiares: Limiares,
+    pub alpha: f64,
+    pub modelo: &'a dyn ModeloProbabilidade,
+}
+
+/// Opções de execução de um cenário
+#[derive(Clone, Debug)]
+pub struct Configuracao {
+    pub grade: Grade,
+    pub raio_vizinhanca: i32,
+    pub formato_vizinhanca: FormatoVizinhanca,
+    /// Desvio padrão, em células, dos pesos da vizinhança gaussiana
+    pub sigma_vizinhanca: f64,
+    pub funcao_densidade: FuncaoDensidade,
+    /// Alpha inicial das formigas
+    pub alpha: f64,
+    /// Liga o ajuste do alpha de cada formiga pela taxa de largadas com falha (ATTA)
+    pub alpha_adaptativo: bool,
+    pub periodo_alpha: u32,
+    pub passo_alpha: f64,
+    pub taxa_falha_alvo: f64,
+    /// Modelo das probabilidades de pegar e largar
+    pub modelo: Arc<dyn ModeloProbabilidade>,
+    pub limiares: Limiares,
+    /// Inclinação usada quando o modelo é o sigmoide
+    pub inclinacao_sigmoide: f64,
+    /// Número máximo de passos da fase de finalização, em que as formigas carregadas
+    /// continuam andando sem pegar grãos até largarem o que seguram.
+    /// `None` desativa a fase e os grãos carregados ficam fora do mapa final.
+    pub passos_finalizacao: Option<i64>,
+    pub modo_atualizacao: ModoAtualizacao,
+    pub algoritmo: Algoritmo,
+    /// Número de agrupamentos em que o resultado é cortado; `None` mantém os naturais
+    pub grupos: Option<usize>,
+    pub num_particulas: usize,
+    pub iteracoes_particulas: usize,
+    /// Pesos da velocidade anterior e das atrações pela melhor posição da partícula e
+    /// do enxame
+    pub inercia_particulas: f64,
+    pub cognitivo_particulas: f64,
+    pub social_particulas: f64,
+    /// Raio da vizinhança do DBSCAN; `None` estima pelos grãos
+    pub raio_dbscan: Option<f64>,
+    /// Pós-processamento dos agrupamentos extraídos, avaliado junto com o resultado original
+    pub refinamento: Option<Refinamento>,
+    /// Arquivo com as restrições entre pares de grãos (`juntos`/`separados`)
+    pub arquivo_restricoes: Option<String>,
+    /// Restrições lidas de `arquivo_restricoes` depois que os grãos são carregados
+    pub restricoes: Arc<Restricoes>,
+    /// Similaridade somada ou subtraída por vizinho com restrição de ficar junto ou separado
+    pub bonus_restricao: f64,
+    pub penalidade_restricao: f64,
+    pub minimo_dbscan: usize,
+    pub num_formigas: i32,
+    /// Populações de formigas com parâmetros próprios; vazia, todas seguem a configuração
+    pub populacoes: Vec<Populacao>,
+    /// Como as formigas andam quando não estão indo para uma célula lembrada
+    pub movimento: Arc<dyn EstrategiaMovimento>,
+    /// Chance de repetir a direção na caminhada persistente
+    pub persistencia: f64,
+    /// Expoente da distribuição dos comprimentos do voo de Lévy
+    pub expoente_levy: f64,
+    /// Células por salto na estratégia de saltos
+    pub velocidade: i32,
+    /// Regra aplicada quando uma formiga falha em largar `limite_estagnacao` vezes seguidas
+    pub regra_estagnacao: RegraEstagnacao,
+    pub limite_estagnacao: u32,
+    /// Raio de busca das regras de troca e de melhor célula
+    pub raio_estagnacao: i32,
+    /// Como os grãos são distribuídos na grade no início
+    pub posicionamento: Posicionamento,
+    /// Impede que duas formigas fiquem na mesma célula
+    pub uma_formiga_por_celula: bool,
+    /// Modelo de pilhas: as células guardam vários grãos e as formigas pegam do topo
+    pub pilhas: bool,
+    /// Liga a camada de feromônio: as formigas depositam ao largar um grão e as sem carga
+    /// preferem andar para as células com mais feromônio
+    pub feromonio: bool,
+    pub deposito_feromonio: f64,
+    pub evaporacao_feromonio: f64,
+    pub difusao_feromonio: f64,
+    /// Passos das formigas entre duas atualizações (evaporação e difusão) do campo
+    pub periodo_feromonio: i64,
+    pub atracao_feromonio: f64,
+    /// Desenha o feromônio nas imagens, em verde, por baixo dos grãos
+    pub desenhar_feromonio: bool,
+    /// Quantos dos grãos mais anômalos do mapa final são circulados na imagem final
+    pub destacar_anomalias: usize,
+    /// Quantos grãos largados cada formiga lembra; `0` desliga a memória
+    pub tamanho_memoria: usize,
+    /// Agendas que, quando presentes, substituem os valores fixos conforme o passo atual
+    pub agenda_k1: Option<Agenda>,
+    pub agenda_k2: Option<Agenda>,
+    pub agenda_alpha: Option<Agenda>,
+    pub agenda_raio: Option<Agenda>,
+    /// Deriva alpha, tamanho da grade e número de formigas dos grãos carregados,
+    /// substituindo os valores dessas opções
+    pub parametros_automaticos: bool,
+}
+
+impl Default for Configuracao {
+    fn default() -> Self {
+        Configuracao {
+            grade: Grade::new(
+                LARGURA_GRADE,
+                ALTURA_GRADE,
+                Fronteira::Toroidal,
+                Topologia::Quadrada,
+            ),
+            raio_vizinhanca: TAMANHO_VIZINHANCA,
+            formato_vizinhanca: FormatoVizinhanca::Moore,
+            sigma_vizinhanca: SIGMA_VIZINHANCA,
+            funcao_densidade: FuncaoDensidade::Original,
+            alpha: ALPHA,
+            alpha_adaptativo: false,
+            periodo_alpha: PERIODO_ALPHA,
+            passo_alpha: PASSO_ALPHA,
+            taxa_falha_alvo: TAXA_FALHA_ALVO,
+            modelo: Arc::new(Quadratico),
+            limiares: Limiares { k1: K1, k2: K2 },
+            inclinacao_sigmoide: INCLINACAO_SIGMOIDE,
+            passos_finalizacao: Some(PASSOS_FINALIZACAO),
+            modo_atualizacao: ModoAtualizacao::Assincrono,
+            algoritmo: Algoritmo::Formigas,
+            grupos: None,
+            num_particulas: NUM_PARTICULAS,
+            iteracoes_particulas: ITERACOES_PARTICULAS,
+            inercia_particulas: INERCIA_PARTICULAS,
+            cognitivo_particulas: COGNITIVO_PARTICULAS,
+            social_particulas: SOCIAL_PARTICULAS,
+            raio_dbscan: None,
+            refinamento: None,
+            arquivo_restricoes: None,
+            restricoes: Arc::new(Restricoes::default()),
+            bonus_restricao: BONUS_RESTRICAO,
+            penalidade_restricao: PENALIDADE_RESTRICAO,
+            minimo_dbscan: MINIMO_DBSCAN,
+            num_formigas: NUM_FORMIGAS,
+            populacoes: vec![],
+            movimento: Arc::new(Uniforme),
+            persistencia: PERSISTENCIA,
+            expoente_levy: EXPOENTE_LEVY,
+            velocidade: VELOCIDADE,
+            tamanho_memoria: 0,
+            regra_estagnacao: RegraEstagnacao::Nenhuma,
+            limite_estagnacao: LIMITE_ESTAGNACAO,
+            raio_estagnacao: RAIO_ESTAGNACAO,
+            posicionamento: Posicionamento::Aleatorio,
+            uma_formiga_por_celula: false,
+            pilhas: false,
+            feromonio: false,
+            deposito_feromonio: DEPOSITO_FEROMONIO,
+            evaporacao_feromonio: EVAPORACAO_FEROMONIO,
+            difusao_feromonio: DIFUSAO_FEROMONIO,
+            periodo_feromonio: PERIODO_FEROMONIO,
+            atracao_feromonio: ATRACAO_FEROMONIO,
+            desenhar_feromonio: false,
+            destacar_anomalias: 0,
+            agenda_k1: None,
+            agenda_k2: None,
+            agenda_alpha: None,
+            agenda_raio: None,
+            parametros_automaticos: false,
+        }
+    }
+}
+
+impl Configuracao {
+    /// Lê um arquivo de cenário com linhas `chave = valor`. Linhas vazias e comentários
+    /// iniciados por `#` são ignorados; as chaves ausentes mantêm o valor padrão.
+    pub fn ler_arquivo(caminho: &str) -> Result<Configuracao, Box<dyn Error>> {
+        let conteudo = fs::read_to_string(caminho)?;
+        let mut configuracao = Configuracao::default();
+
+        for (numero, linha) in conteudo.lines().enumerate() {
+            let linha = linha.split('#').next().unwrap_or("").trim();
+            if linha.is_empty() {
+                continue;
+            }
+
+            let (chave, valor) = linha
+                .split_once('=')
+                .ok_or_else(|| format!("Linha {}: esperado `chave = valor`", numero + 1))?;
+            configuracao
+                .definir(chave.trim(), valor.trim())
+                .map_err(|e| format!("Linha {}: {}", numero + 1, e))?;
+        }
+
+        if configuracao.grade.largura <= 0 || configuracao.grade.altura <= 0 {
+            return Err("A grade precisa ter largura e altura positivas".into());
+        }
+        if configuracao.grade.topologia == Topologia::Hexagonal
+            && configuracao.grade.fronteira == Fronteira::Toroidal
+            && configuracao.grade.altura % 2 != 0
+        {
+            // Com altura ímpar, dar a volta na vertical troca a paridade das linhas
+            return Err("A grade hexagonal toroidal precisa de altura par".into());
+        }
+        if !(0.0..=1.0).contains(&configuracao.evaporacao_feromonio)
+            || !(0.0..=1.0).contains(&configuracao.difusao_feromonio)
+        {
+            // Frações maiores que 1 deixariam o campo negativo
+            return Err("A evaporação e a difusão do feromônio precisam estar entre 0 e 1".into());
+        }
+        if configuracao.sigma_vizinhanca <= 0.0 {
+            // Com desvio zero o peso da célula central seria exp(-0/0), que não é um número
+            return Err("O desvio da vizinhança gaussiana precisa ser positivo".into());
+        }
+        if configuracao.alpha_adaptativo && configuracao.agenda_alpha.is_some() {
+            return Err(
+                "O alpha adaptativo não pode ser usado junto com uma agenda de alpha".into(),
+            );
+        }
+        if configuracao.pilhas && configuracao.arquivo_restricoes.is_some() {
+            // Os vizinhos no modelo de pilhas são os centroides, que não têm id de grão
+            return Err("As restrições não podem ser usadas junto com o modelo de pilhas".into());
+        }
+
+        Ok(configuracao)
+    }
+
+    /// Altera uma opção pelo nome usado nos arquivos de cenário
+    pub fn definir(&mut self, chave: &str, valor: &str) -> Result<(), Box<dyn Error>> {
+        match chave {
+            "largura" => self.grade.largura = valor.parse()?,
+            "altura" => self.grade.altura = valor.parse()?,
+            "fronteira" => self.grade.fronteira = valor.parse()?,
+            "topologia" => self.grade.topologia = valor.parse()?,
+            "raio_vizinhanca" => self.raio_vizinhanca = ler_no_minimo(valor, 0)?,
+            "formato_vizinhanca" => self.formato_vizinhanca = valor.parse()?,
+            "sigma_vizinhanca" => self.sigma_vizinhanca = valor.parse()?,
+            "funcao_densidade" => self.funcao_densidade = valor.parse()?,
+            "alpha" => self.alpha = valor.parse()?,
+            "alpha_adaptativo" => self.alpha_adaptativo = ler_booleano(valor)?,
+            "periodo_alpha" => self.periodo_alpha = valor.parse()?,
+            "passo_alpha" => self.passo_alpha = valor.parse()?,
+            "taxa_falha_alvo" => self.taxa_falha_alvo = valor.parse()?,
+            "modelo" => self.modelo = modelo_por_nome(valor, self.inclinacao_sigmoide)?,
+            "inclinacao_sigmoide" => {
+                self.inclinacao_sigmoide = valor.parse()?;
+                self.modelo = modelo_por_nome(self.modelo.nome(), self.inclinacao_sigmoide)?;
+            }
+            "k1" => self.limiares.k1 = valor.parse()?,
+            "k2" => self.limiares.k2 = valor.parse()?,
+            "passos_finalizacao" => self.passos_finalizacao = ler_opcional(valor)?,
+            "modo_atualizacao" => self.modo_atualizacao = valor.parse()?,
+            "algoritmo" => self.algoritmo = valor.parse()?,
+            "grupos" => self.grupos = ler_opcional(valor)?,
+            "num_particulas" => self.num_particulas = valor.parse()?,
+            "iteracoes_particulas" => self.iteracoes_particulas = valor.parse()?,
+            "inercia_particulas" => self.inercia_particulas = valor.parse()?,
+            "cognitivo_particulas" => self.cognitivo_particulas = valor.parse()?,
+            "social_particulas" => self.social_particulas = valor.parse()?,
+            "raio_dbscan" => self.raio_dbscan = ler_opcional(valor)?,
+            "refinamento" => self.refinamento = ler_opcional(valor)?,
+            "restricoes" => self.arquivo_restricoes = ler_opcional(valor)?,
+            "bonus_restricao" => self.bonus_restricao = valor.parse()?,
+            "penalidade_restricao" => self.penalidade_restricao = valor.parse()?,
+            "minimo_dbscan" => self.minimo_dbscan = valor.parse()?,
+            "num_formigas" => self.num_formigas = valor.parse()?,
+            // Cada linha acrescenta uma população; `nenhum` apaga as anteriores
+            "populacao" => match ler_opcional(valor)? {
+                Some(populacao) => self.populacoes.push(populacao),
+                None => self.populacoes.clear(),
+            },
+            "movimento" => self.movimento = self.criar_movimento(valor)?,
+            "persistencia" => {
+                self.persistencia = valor.parse()?;
+                self.movimento = self.criar_movimento(self.movimento.nome())?;
+            }
+            "expoente_levy" => {
+                self.expoente_levy = valor.parse()?;
+                self.movimento = self.criar_movimento(self.movimento.nome())?;
+            }
+            "velocidade" => {
+                self.velocidade = ler_no_minimo(valor, 1)?;
+                self.movimento = self.criar_movimento(self.movimento.nome())?;
+            }
+            "tamanho_memoria" => self.tamanho_memoria = valor.parse()?,
+            "regra_estagnacao" => self.regra_estagnacao = valor.parse()?,
+            "limite_estagnacao" => self.limite_estagnacao = valor.parse()?,
+            "raio_estagnacao" => self.raio_estagnacao = ler_no_minimo(valor, 0)?,
+            "posicionamento" => self.posicionamento = valor.parse()?,
+            "uma_formiga_por_celula" => self.uma_formiga_por_celula = ler_booleano(valor)?,
+            "pilhas" => self.pilhas = ler_booleano(valor)?,
+            "feromonio" => self.feromonio = ler_booleano(valor)?,
+            "deposito_feromonio" => self.deposito_feromonio = valor.parse()?,
+            "evaporacao_feromonio" => self.evaporacao_feromonio = valor.parse()?,
+            "difusao_feromonio" => self.difusao_feromonio = valor.parse()?,
+            "periodo_feromonio" => self.periodo_feromonio = valor.parse()?,
+            "atracao_feromonio" => self.atracao_feromonio = valor.parse()?,
+            "desenhar_feromonio" => self.desenhar_feromonio = ler_booleano(valor)?,
+            "destacar_anomalias" => self.destacar_anomalias = valor.parse()?,
+            "agenda_k1" => self.agenda_k1 = ler_opcional(valor)?,
+            "agenda_k2" => self.agenda_k2 = ler_opcional(valor)?,
+            "agenda_alpha" => self.agenda_alpha = ler_opcional(valor)?,
+            "agenda_raio" => self.agenda_raio = ler_opcional(valor)?,
+            "parametros_automaticos" => self.parametros_automaticos = ler_booleano(valor)?,
+            _ => return Err(format!("opção desconhecida `{}`", chave).into()),
+        }
+
+        Ok(())
+    }
+
+    fn criar_movimento(&self, nome: &str) -> Result<Arc<dyn EstrategiaMovimento>, String> {
+        movimento_por_nome(nome, self.persistencia, self.expoente_levy, self.velocidade)
+    }
+
+    /// Parâmetros em vigor no passo `passo` para uma formiga com o alpha `alpha` e o perfil
+    /// `perfil`. As agendas presentes substituem os valores fixos (o raio da agenda é
+    /// arredondado), e o raio e o modelo do perfil substituem os da configuração.
+    pub fn parametros_no_passo<'a>(
+        &'a self,
+        passo: i64,
+        alpha: f64,
+        perfil: &'a Perfil,
+    ) -> ParametrosPasso<'a> {
+        let valor = |agenda: &Option<Agenda>, fixo: f64| {
+            agenda.as_ref().map_or(fixo, |agenda| agenda.valor(passo))
+        };
+
+        ParametrosPasso {
+            raio_vizinhanca: match (perfil.raio_vizinhanca, &self.agenda_raio) {
+                (Some(raio), _) => raio,
+                (None, Some(agenda)) => (agenda.valor(passo).round() as i32).max(0),
+                (None, None) => self.raio_vizinhanca,
+            },
+            limiares: Limiares {
+                k1: valor(&self.agenda_k1, self.limiares.k1),
+                k2: valor(&self.agenda_k2, self.limiares.k2),
+            },
+            alpha: valor(&self.agenda_alpha, alpha),
+            modelo: perfil.modelo.as_deref().unwrap_or(&*self.modelo),
+        }
+    }
+
+    /// Troca alpha, grade e número de formigas pelos valores estimados dos dados. A grade
+    /// fica quadrada e, se for hexagonal toroidal, com lado par.
+    pub fn aplicar_parametros_automaticos(&mut self, parametros: &ParametrosAutomaticos) {
+        let mut lado = parametros.lado_grade;
+        if self.grade.topologia == Topologia::Hexagonal
+            && self.grade.fronteira == Fronteira::Toroidal
+            && lado % 2 != 0
+        {
+            lado += 1;
+        }
+
+        self.alpha = parametros.alpha;
+        self.grade.largura = lado;
+        self.grade.altura = lado;
+        self.num_formigas = parametros.num_formigas;
+    }
+
+    /// Manifesto da execução: as opções usadas, no formato dos arquivos de cenário, de
+    /// forma que o arquivo possa ser lido de volta para repetir a execução
+    pub fn manifesto(&self) -> String {
+        let mut texto = String::new();
+        let _ = writeln!(texto, "# Manifesto da execução");
+
+        if self.parametros_automaticos {
+            // Os valores derivados já estão nas opções abaixo; relê-los não deve recalculá-los
+            let _ = writeln!(
+                texto,
+                "# alpha, largura, altura e num_formigas foram derivados dos dados"
+            );
+        }
+        let _ = writeln!(texto, "parametros_automaticos = nao");
+
+        let mut opcoes: Vec<(&str, String)> = vec![
+            ("largura", self.grade.largura.to_string()),
+            ("altura", self.grade.altura.to_string()),
+            ("fronteira", self.grade.fronteira.to_string()),
+            ("topologia", self.grade.topologia.to_string()),
+            ("num_formigas", self.num_formigas.to_string()),
+            ("tamanho_memoria", self.tamanho_memoria.to_string()),
+            ("regra_estagnacao", self.regra_estagnacao.to_string()),
+            ("limite_estagnacao", self.limite_estagnacao.to_string()),
+            ("raio_estagnacao", self.raio_estagnacao.to_string()),
+            ("posicionamento", self.posicionamento.to_string()),
+            (
+                "uma_formiga_por_celula",
+                escrever_booleano(self.uma_formiga_por_celula),
+            ),
+            ("pilhas", escrever_booleano(self.pilhas)),
+            ("feromonio", escrever_booleano(self.feromonio)),
+            ("deposito_feromonio", self.deposito_feromonio.to_string()),
+            (
+                "evaporacao_feromonio",
+                self.evaporacao_feromonio.to_string(),
+            ),
+            ("difusao_feromonio", self.difusao_feromonio.to_string()),
+            ("periodo_feromonio", self.periodo_feromonio.to_string()),
+            ("atracao_feromonio", self.atracao_feromonio.to_string()),
+            (
+                "desenhar_feromonio",
+                escrever_booleano(self.desenhar_feromonio),
+            ),
+            ("destacar_anomalias", self.destacar_anomalias.to_string()),
+            ("persistencia", self.persistencia.to_string()),
+            ("expoente_levy", self.expoente_levy.to_string()),
+            ("velocidade", self.velocidade.to_string()),
+            ("movimento", self.movimento.nome().to_string()),
+            ("raio_vizinhanca", self.raio_vizinhanca.to_string()),
+            ("formato_vizinhanca", self.formato_vizinhanca.to_string()),
+            ("sigma_vizinhanca", self.sigma_vizinhanca.to_string()),
+            ("funcao_densidade", self.funcao_densidade.to_string()),
+            ("alpha", self.alpha.to_string()),
+            ("alpha_adaptativo", escrever_booleano(self.alpha_adaptativo)),
+            ("periodo_alpha", self.periodo_alpha.to_string()),
+            ("passo_alpha", self.passo_alpha.to_string()),
+            ("taxa_falha_alvo", self.taxa_falha_alvo.to_string()),
+            ("inclinacao_sigmoide", self.inclinacao_sigmoide.to_string()),
+            ("modelo", self.modelo.nome().to_string()),
+            ("k1", self.limiares.k1.to_string()),
+            ("k2", self.limiares.k2.to_string()),
+            (
+                "passos_finalizacao",
+                escrever_opcional(&self.passos_finalizacao),
+            ),
+            ("modo_atualizacao", self.modo_atualizacao.to_string()),
+            ("algoritmo", self.algoritmo.to_string()),
+            ("grupos", escrever_opcional(&self.grupos)),
+            ("num_particulas", self.num_particulas.to_string()),
+            (
+                "iteracoes_particulas",
+                self.iteracoes_particulas.to_string(),
+            ),
+            ("inercia_particulas", self.inercia_particulas.to_string()),
+            (
+                "cognitivo_particulas",
+                self.cognitivo_particulas.to_string(),
+            ),
+            ("social_particulas", self.social_particulas.to_string()),
+            ("raio_dbscan", escrever_opcional(&self.raio_dbscan)),
+            ("refinamento", escrever_opcional(&self.refinamento)),
+            ("restricoes", escrever_opcional(&self.arquivo_restricoes)),
+            ("bonus_restricao", self.bonus_restricao.to_string()),
+            (
+                "penalidade_restricao",
+                self.penalidade_restricao.to_string(),
+            ),
+            ("minimo_dbscan", self.minimo_dbscan.to_string()),
+            ("agenda_k1", escrever_opcional(&self.agenda_k1)),
+            ("agenda_k2", escrever_opcional(&self.agenda_k2)),
+            ("agenda_alpha", escrever_opcional(&self.agenda_alpha)),
+            ("agenda_raio", escrever_opcional(&self.agenda_raio)),
+        ];
+        opcoes.extend(
+            self.populacoes
+                .iter()
+                .map(|populacao| ("populacao", populacao.to_string())),
+        );
+        for (chave, valor) in opcoes {
+            let _ = writeln!(texto, "{} = {}", chave, valor);
+        }
+
+        texto
+    }
+}
+
+/// Lê `sim` ou `nao`
+fn ler_booleano(valor: &str) -> Result<bool, String> {
+    match valor {
+        "sim" => Ok(true),
+        "nao" => Ok(false),
+        _ => Err(format!("esperado `sim` ou `nao`, encontrado `{}`", valor)),
+    }
+}
+
+/// Lê um inteiro que não pode ser menor que `minimo`, como os raios (0) e as velocidades
+/// (1), também nas populações
+fn ler_no_minimo(valor: &str, minimo: i32) -> Result<i32, Box<dyn Error>> {
+    let numero: i32 = valor.parse()?;
+    if numero < minimo {
+        return Err(format!(
+            "o valor precisa ser pelo menos {}, encontrado {}",
+            minimo, numero
+        )
+        .into());
+    }
+
+    Ok(numero)
+}
+
+fn escrever_booleano(valor: bool) -> String {
+    if valor { "sim" } else { "nao" }.to_string()
+}
+
+fn escrever_opcional<T: fmt::Display>(valor: &Option<T>) -> String {
+    valor
+        .as_ref()
+        .map_or("nenhum".to_string(), |valor| valor.to_string())
+}
+
+/// Lê um valor que pode ser desativado com `nenhum`
+fn ler_opcional<T: FromStr>(valor: &str) -> Result<Option<T>, T::Err> {
+    if valor == "nenhum" {
+        Ok(None)
+    } else {
+        valor.parse().map(Some)
+    }
+}
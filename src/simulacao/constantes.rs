@@ -0,0 +1,37 @@
+// Parametros
+pub const LARGURA_GRADE: i32 = 64; // Número de células da grade na horizontal
+pub const ALTURA_GRADE: i32 = 64; // Número de células da grade na vertical
+pub const TAMANHO_VIZINHANCA: i32 = 1; // Definindo o tamanho da vizinhança (a distância máxima em cada direção)
+pub const SIGMA_VIZINHANCA: f64 = 1.0; // Desvio padrão dos pesos da vizinhança gaussiana
+pub const ALPHA: f64 = 12.0;
+pub const PERIODO_ALPHA: u32 = 100; // Passos de cada formiga entre dois ajustes do alpha adaptativo
+pub const PASSO_ALPHA: f64 = 0.01; // Quanto o alpha adaptativo muda em cada ajuste
+pub const TAXA_FALHA_ALVO: f64 = 0.99; // Fração de largadas com falha acima da qual o alpha cresce
+pub const K1: f64 = 0.5;
+pub const K2: f64 = 0.5;
+pub const INCLINACAO_SIGMOIDE: f64 = 2.0; // Inclinação do modelo de probabilidade sigmoide
+pub const NUM_FORMIGAS: i32 = 40;
+pub const LIMITE_ESTAGNACAO: u32 = 1_000; // Tentativas seguidas de largar sem sucesso até a regra de estagnação agir
+pub const RAIO_ESTAGNACAO: i32 = 2; // Raio de busca das regras de estagnação
+pub const DEPOSITO_FEROMONIO: f64 = 1.0; // Feromônio deixado a cada grão largado
+pub const EVAPORACAO_FEROMONIO: f64 = 0.05; // Fração do feromônio que evapora a cada atualização do campo
+pub const DIFUSAO_FEROMONIO: f64 = 0.1; // Fração do feromônio trocada com as células vizinhas a cada atualização
+pub const PERIODO_FEROMONIO: i64 = 10_000; // Passos das formigas entre duas atualizações do campo
+pub const ATRACAO_FEROMONIO: f64 = 1.0; // Peso do feromônio na escolha da direção das formigas sem carga
+pub const PERSISTENCIA: f64 = 0.8; // Chance de manter a direção na caminhada persistente
+pub const EXPOENTE_LEVY: f64 = 2.0; // Expoente dos comprimentos do voo de Lévy
+pub const VELOCIDADE: i32 = 2; // Células por salto na estratégia de saltos
+pub const AMOSTRA_PARAMETROS: usize = 200; // Grãos sorteados para estimar o alpha nos parâmetros automáticos
+pub const NUM_INTERACOES: i64 = 50_000_000;
+pub const PASSOS_FINALIZACAO: i64 = 100_000; // Limite de passos da fase de finalização por formiga
+pub const PASSOS_POR_RESERVA: i64 = 256; // Passos que cada thread reserva de uma vez do contador do lote
+pub const PASSOS_POR_LOTE: i64 = 1_000_000; // Passos executados pelo conjunto de threads entre dois pontos de controle do cenário
+pub const NUM_PARTICULAS: usize = 20; // Partículas do agrupamento por enxame de partículas
+pub const ITERACOES_PARTICULAS: usize = 200; // Iterações do enxame de partículas
+pub const INERCIA_PARTICULAS: f64 = 0.72; // Peso da velocidade anterior das partículas
+pub const COGNITIVO_PARTICULAS: f64 = 1.49; // Atração de cada partícula pela sua melhor posição
+pub const SOCIAL_PARTICULAS: f64 = 1.49; // Atração das partículas pela melhor posição do enxame
+pub const ITERACOES_KMEDIAS: usize = 100; // Limite de iterações do k-means quando ele não converge antes
+pub const MINIMO_DBSCAN: usize = 4; // Vizinhos dentro do raio para um grão ser núcleo no DBSCAN
+pub const BONUS_RESTRICAO: f64 = 0.5; // Similaridade somada para cada vizinho com restrição de ficar junto
+pub const PENALIDADE_RESTRICAO: f64 = 0.5; // Similaridade subtraída para cada vizinho com restrição de ficar separado
@@ -0,0 +1,233 @@
+use super::configuracao::{Configuracao, ParametrosPasso};
+use super::grao::Grao;
+use super::mapa::Vizinhanca;
+use super::restricoes::Restricao;
+use std::fmt;
+use std::str::FromStr;
+
+/// Função de densidade local f(oᵢ), que mede o quanto um grão combina com a vizinhança
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FuncaoDensidade {
+    /// Fórmula original do projeto. Pela precedência dos operadores ela calcula
+    /// `Σ((1 - d) / α) / 2 · raio + 1`, e dá 0 quando não há vizinhos
+    Original,
+    /// Fórmula de Lumer e Faieta: `max(0, 1/s² · Σ (1 - d/α))`, em que s² é o número de
+    /// células da vizinhança (a soma dos pesos, na vizinhança gaussiana) e o próprio grão
+    /// não conta como vizinho. Uma vizinhança vazia tem densidade 0.
+    LumerFaieta,
+}
+
+impl FromStr for FuncaoDensidade {
+    type Err = String;
+
+    fn from_str(valor: &str) -> Result<Self, Self::Err> {
+        match valor {
+            "original" => Ok(FuncaoDensidade::Original),
+            "lumer_faieta" => Ok(FuncaoDensidade::LumerFaieta),
+            _ => Err(format!("função de densidade desconhecida `{}`", valor)),
+        }
+    }
+}
+
+impl fmt::Display for FuncaoDensidade {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nome = match self {
+            FuncaoDensidade::Original => "original",
+            FuncaoDensidade::LumerFaieta => "lumer_faieta",
+        };
+        write!(f, "{}", nome)
+    }
+}
+
+pub fn distancia_entre_par_de_dados(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(i, j)| (*i - *j).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Similaridade local do grão com os vizinhos, pela função de densidade configurada e
+/// com o raio e o alpha em vigor no passo. Cada vizinho contribui conforme o peso da sua
+/// célula, que só é diferente de 1 na vizinhança gaussiana.
+pub fn similaridade(
+    grao: &Grao,
+    graos_perto: &Vizinhanca,
+    configuracao: &Configuracao,
+    parametros: &ParametrosPasso,
+) -> f64 {
+    match configuracao.funcao_densidade {
+        FuncaoDensidade::Original => similaridade_original(
+            grao,
+            graos_perto,
+            configuracao,
+            parametros.raio_vizinhanca,
+            parametros.alpha,
+        ),
+        FuncaoDensidade::LumerFaieta => {
+            similaridade_lumer_faieta(grao, graos_perto, configuracao, parametros.alpha)
+        }
+    }
+}
+
+/// Bônus (restrição de ficarem juntos) ou penalidade (de ficarem separados) somado à
+/// contribuição de um vizinho
+fn ajuste_restricao(grao: &Grao, vizinho: &Grao, configuracao: &Configuracao) -> f64 {
+    match configuracao.restricoes.entre(&grao.id, &vizinho.id) {
+        Some(Restricao::Juntos) => configuracao.bonus_restricao,
+        Some(Restricao::Separados) => -configuracao.penalidade_restricao,
+        None => 0.0,
+    }
+}
+
+fn similaridade_original(
+    grao: &Grao,
+    graos_perto: &Vizinhanca,
+    configuracao: &Configuracao,
+    raio: i32,
+    alpha: f64,
+) -> f64 {
+    let quantidade_dados: usize = graos_perto.graos.len();
+
+    if quantidade_dados > 0 {
+        let resultado: f64 = graos_perto
+            .graos
+            .iter()
+            .map(|(g, peso)| {
+                peso * ((1.0 - distancia_entre_par_de_dados(&grao.dados, &g.dados)) / alpha
+                    + ajuste_restricao(grao, g, configuracao))
+            })
+            .sum::<f64>()
+            / 2.0
+            * raio as f64
+            + 1.0;
+
+        if resultado > 0.0 {
+            return resultado;
+        } else {
+            return 0.0;
+        }
+    }
+
+    0.0
+}
+
+fn similaridade_lumer_faieta(
+    grao: &Grao,
+    graos_perto: &Vizinhanca,
+    configuracao: &Configuracao,
+    alpha: f64,
+) -> f64 {
+    if graos_perto.peso_total <= 0.0 {
+        return 0.0;
+    }
+
+    let soma: f64 = graos_perto
+        .graos
+        .iter()
+        .filter(|(g, _)| g.id != grao.id)
+        .map(|(g, peso)| {
+            peso * (1.0 - distancia_entre_par_de_dados(&grao.dados, &g.dados) / alpha
+                + ajuste_restricao(grao, g, configuracao))
+        })
+        .sum();
+
+    (soma / graos_perto.peso_total).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::grade::{FormatoVizinhanca, Fronteira, Grade, Topologia};
+    use crate::simulacao::mapa::Mapa;
+    use crate::simulacao::outros::Ponto;
+
+    fn com_valor(valor: f64) -> Grao {
+        Grao::new(Ponto { x: 1, y: 1 }, vec![valor], 0)
+    }
+
+    fn vizinhanca(graos: &[(Grao, f64)], peso_total: f64) -> Vizinhanca {
+        Vizinhanca {
+            graos: graos.to_vec(),
+            peso_total,
+        }
+    }
+
+    #[test]
+    fn lumer_faieta_vizinhanca_vazia_da_zero() {
+        let configuracao = Configuracao::default();
+        let grao = com_valor(0.0);
+
+        for peso_total in [0.0, 9.0] {
+            let vazia = vizinhanca(&[], peso_total);
+            assert_eq!(
+                similaridade_lumer_faieta(&grao, &vazia, &configuracao, 1.0),
+                0.0
+            );
+        }
+
+        // Só o próprio grão na vizinhança
+        let sozinho = vizinhanca(&[(grao.clone(), 1.0)], 9.0);
+        assert_eq!(
+            similaridade_lumer_faieta(&grao, &sozinho, &configuracao, 1.0),
+            0.0
+        );
+    }
+
+    #[test]
+    fn lumer_faieta_divide_por_s2() {
+        let configuracao = Configuracao::default();
+        let grao = com_valor(0.0);
+
+        // (1 - 0.5/1) + (1 - 0/1) sobre as 9 células da vizinhança de raio 1
+        let perto = vizinhanca(
+            &[
+                (grao.clone(), 1.0),
+                (com_valor(0.5), 1.0),
+                (com_valor(0.0), 1.0),
+            ],
+            9.0,
+        );
+        let f = similaridade_lumer_faieta(&grao, &perto, &configuracao, 1.0);
+        assert!((f - 1.5 / 9.0).abs() < 1e-12);
+
+        // Com alpha 2 a distância pesa metade: (1 - 0.25) + 1
+        let f = similaridade_lumer_faieta(&grao, &perto, &configuracao, 2.0);
+        assert!((f - 1.75 / 9.0).abs() < 1e-12);
+
+        // O peso da célula multiplica a contribuição e a soma dos pesos substitui s²
+        let ponderada = vizinhanca(&[(com_valor(0.0), 0.5)], 2.0);
+        let f = similaridade_lumer_faieta(&grao, &ponderada, &configuracao, 1.0);
+        assert!((f - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn lumer_faieta_nao_fica_negativa() {
+        let configuracao = Configuracao::default();
+        let grao = com_valor(0.0);
+
+        let distantes = vizinhanca(&[(com_valor(3.0), 1.0), (com_valor(0.5), 1.0)], 9.0);
+        assert_eq!(
+            similaridade_lumer_faieta(&grao, &distantes, &configuracao, 1.0),
+            0.0
+        );
+    }
+
+    #[test]
+    fn s2_e_o_numero_de_celulas_da_vizinhanca() {
+        let grade = Grade::new(10, 10, Fronteira::Toroidal, Topologia::Quadrada);
+        let mapa = Mapa::new(grade, vec![com_valor(0.0)]);
+
+        for (raio, celulas) in [(1, 9.0), (2, 25.0)] {
+            let vizinhanca = mapa.graos_redor(
+                &Ponto { x: 1, y: 1 },
+                raio,
+                FormatoVizinhanca::Moore,
+                1.0,
+                None,
+            );
+            assert_eq!(vizinhanca.peso_total, celulas);
+            assert_eq!(vizinhanca.graos.len(), 1);
+        }
+    }
+}
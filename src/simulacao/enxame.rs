@@ -0,0 +1,38 @@
+use super::avaliacao::{avaliar, Avaliacao};
+use super::grao::Grao;
+
+/// Algoritmo de enxame que agrupa grãos. A execução é dividida em passos para que quem
+/// conduz possa acompanhar o progresso; o que cada passo faz fica a cargo do algoritmo.
+pub trait AlgoritmoEnxame {
+    /// Nome usado nas mensagens
+    fn nome(&self) -> &'static str;
+
+    /// Prepara a execução, antes do primeiro passo
+    fn inicializar(&mut self);
+
+    /// Avança a execução; o último passo também faz o fechamento do algoritmo
+    fn passo(&mut self);
+
+    /// Se não há mais passos a executar
+    fn terminou(&self) -> bool;
+
+    /// Grãos agrupados e o rótulo do agrupamento de cada um, na mesma ordem
+    fn agrupamentos(&self) -> (Vec<Grao>, Vec<usize>);
+}
+
+/// Executa o algoritmo até o fim e avalia os agrupamentos encontrados
+pub fn executar(algoritmo: &mut dyn AlgoritmoEnxame) -> Avaliacao {
+    algoritmo.inicializar();
+    while !algoritmo.terminou() {
+        algoritmo.passo();
+    }
+
+    let (graos, rotulos) = algoritmo.agrupamentos();
+    avaliar(&graos, &rotulos)
+}
+
+/// Número de agrupamentos dos algoritmos que precisam dele quando `grupos` é `nenhum`:
+/// √(n/2) para n grãos
+pub fn grupos_padrao(quantidade_graos: usize) -> usize {
+    ((quantidade_graos / 2) as f64).sqrt().round().max(1.0) as usize
+}
@@ -0,0 +1,173 @@
+use super::configuracao::{Configuracao, ParametrosPasso};
+use super::densidade::similaridade;
+use super::grao::Grao;
+use super::mapa::Mapa;
+use super::outros::Ponto;
+use std::fmt;
+use std::str::FromStr;
+
+/// O que fazer com a formiga que falhou em largar o grão muitas vezes seguidas
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RegraEstagnacao {
+    /// A formiga continua tentando indefinidamente
+    Nenhuma,
+    /// Larga o grão na célula atual ou, se ocupada, na livre mais próxima
+    LargarForcado,
+    /// Troca o grão carregado pelo grão mais deslocado dentro do raio, se o carregado
+    /// combinar melhor com aquela célula
+    Trocar,
+    /// Larga na célula livre (no modelo de pilhas, em qualquer célula) dentro do raio em
+    /// que o grão tem a maior similaridade
+    MelhorCelula,
+}
+
+impl FromStr for RegraEstagnacao {
+    type Err = String;
+
+    fn from_str(valor: &str) -> Result<Self, Self::Err> {
+        match valor {
+            "nenhuma" => Ok(RegraEstagnacao::Nenhuma),
+            "largar" => Ok(RegraEstagnacao::LargarForcado),
+            "trocar" => Ok(RegraEstagnacao::Trocar),
+            "melhor_celula" => Ok(RegraEstagnacao::MelhorCelula),
+            _ => Err(format!("regra de estagnação desconhecida `{}`", valor)),
+        }
+    }
+}
+
+impl fmt::Display for RegraEstagnacao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nome = match self {
+            RegraEstagnacao::Nenhuma => "nenhuma",
+            RegraEstagnacao::LargarForcado => "largar",
+            RegraEstagnacao::Trocar => "trocar",
+            RegraEstagnacao::MelhorCelula => "melhor_celula",
+        };
+        write!(f, "{}", nome)
+    }
+}
+
+/// Aplica a regra de estagnação configurada à formiga em `posicao` carregando `objeto`.
+/// Se a regra agiu, retorna a célula onde o grão carregado ficou, que pode não ser a da
+/// formiga.
+pub fn resolver_estagnacao(
+    posicao: &Ponto,
+    objeto: &mut Option<Grao>,
+    mapa: &Mapa,
+    configuracao: &Configuracao,
+    parametros: &ParametrosPasso,
+) -> Option<Ponto> {
+    let grao = objeto.take()?;
+
+    let resultado = match configuracao.regra_estagnacao {
+        RegraEstagnacao::Nenhuma => Err(grao),
+        RegraEstagnacao::LargarForcado => largar_forcado(posicao, grao, mapa),
+        RegraEstagnacao::Trocar => trocar(posicao, grao, mapa, configuracao, parametros),
+        RegraEstagnacao::MelhorCelula => {
+            largar_na_melhor_celula(posicao, grao, mapa, configuracao, parametros)
+        }
+    };
+
+    match resultado {
+        Ok((celula, novo)) => {
+            *objeto = novo;
+            Some(celula)
+        }
+        Err(grao) => {
+            *objeto = Some(grao);
+            None
+        }
+    }
+}
+
+/// Similaridade que `grao` teria na célula `local`
+fn similaridade_em(
+    grao: &Grao,
+    local: &Ponto,
+    mapa: &Mapa,
+    configuracao: &Configuracao,
+    parametros: &ParametrosPasso,
+) -> f64 {
+    let vizinhanca = mapa.graos_redor(
+        local,
+        parametros.raio_vizinhanca,
+        configuracao.formato_vizinhanca,
+        configuracao.sigma_vizinhanca,
+        Some(grao.id),
+    );
+
+    similaridade(grao, &vizinhanca, configuracao, parametros)
+}
+
+// As regras retornam a célula onde o grão carregado ficou e o grão que a formiga passa a
+// carregar, ou devolvem o grão se não agiram
+
+fn largar_forcado(posicao: &Ponto, grao: Grao, mapa: &Mapa) -> Result<(Ponto, Option<Grao>), Grao> {
+    let grao = match mapa.largar(posicao, grao) {
+        Ok(()) => return Ok((*posicao, None)),
+        Err(grao) => grao,
+    };
+
+    match mapa.celula_livre_proxima(posicao) {
+        Some(celula) => mapa.largar(&celula, grao).map(|_| (celula, None)),
+        // Mapa cheio: empilha no próprio local
+        None => {
+            mapa.empilhar(posicao, grao);
+            Ok((*posicao, None))
+        }
+    }
+}
+
+fn trocar(
+    posicao: &Ponto,
+    grao: Grao,
+    mapa: &Mapa,
+    configuracao: &Configuracao,
+    parametros: &ParametrosPasso,
+) -> Result<(Ponto, Option<Grao>), Grao> {
+    // Grão mais deslocado do raio, com a similaridade que o carregado teria no lugar dele
+    let candidato = mapa
+        .grade()
+        .vizinhanca(posicao, configuracao.raio_estagnacao)
+        .into_iter()
+        .filter_map(|(celula, _)| mapa.grao_local(&celula).map(|vizinho| (celula, vizinho)))
+        .map(|(celula, vizinho)| {
+            let atual = similaridade_em(&vizinho, &celula, mapa, configuracao, parametros);
+            (celula, vizinho, atual)
+        })
+        .min_by(|a, b| a.2.total_cmp(&b.2));
+
+    match candidato {
+        Some((celula, vizinho, atual))
+            if similaridade_em(&grao, &celula, mapa, configuracao, parametros) > atual =>
+        {
+            mapa.trocar(&celula, vizinho.id, grao)
+                .map(|retirado| (celula, Some(retirado)))
+        }
+        _ => Err(grao),
+    }
+}
+
+fn largar_na_melhor_celula(
+    posicao: &Ponto,
+    grao: Grao,
+    mapa: &Mapa,
+    configuracao: &Configuracao,
+    parametros: &ParametrosPasso,
+) -> Result<(Ponto, Option<Grao>), Grao> {
+    let melhor = mapa
+        .grade()
+        .vizinhanca(posicao, configuracao.raio_estagnacao)
+        .into_iter()
+        .filter(|(celula, _)| mapa.aceita_grao(celula))
+        .map(|(celula, _)| {
+            let f = similaridade_em(&grao, &celula, mapa, configuracao, parametros);
+            (celula, f)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+
+    match melhor {
+        Some((celula, _)) => mapa.largar(&celula, grao).map(|_| (celula, None)),
+        None => Err(grao),
+    }
+}
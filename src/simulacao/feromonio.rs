@@ -0,0 +1,81 @@
+use super::grade::Grade;
+use super::outros::Ponto;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Camada de feromônio sobre a grade. As formigas depositam nas células sem lock: cada
+/// valor é um `f64` guardado nos bits de um `AtomicU64`. A evaporação e a difusão são
+/// aplicadas pelo cenário entre dois trechos de passos, quando as formigas estão paradas.
+#[derive(Debug)]
+pub struct CampoFeromonio {
+    grade: Grade,
+    valores: Vec<AtomicU64>,
+}
+
+impl CampoFeromonio {
+    pub fn new(grade: Grade) -> Self {
+        CampoFeromonio {
+            grade,
+            valores: (0..grade.num_celulas())
+                .map(|_| AtomicU64::new(0f64.to_bits()))
+                .collect(),
+        }
+    }
+
+    /// Quantidade de feromônio na célula; fora da grade é zero
+    pub fn valor(&self, local: &Ponto) -> f64 {
+        self.grade.indice(local).map_or(0.0, |i| {
+            f64::from_bits(self.valores[i].load(Ordering::Relaxed))
+        })
+    }
+
+    pub fn depositar(&self, local: &Ponto, quantidade: f64) {
+        if let Some(i) = self.grade.indice(local) {
+            let _ = self.valores[i].fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                Some((f64::from_bits(bits) + quantidade).to_bits())
+            });
+        }
+    }
+
+    /// Maior quantidade de feromônio numa célula
+    pub fn maximo(&self) -> f64 {
+        self.valores
+            .iter()
+            .map(|valor| f64::from_bits(valor.load(Ordering::Relaxed)))
+            .fold(0.0, f64::max)
+    }
+
+    /// Um passo do campo: cada célula troca a fração `difusao` do seu feromônio pela média
+    /// das células vizinhas e depois perde a fração `evaporacao`
+    pub fn atualizar(&self, evaporacao: f64, difusao: f64) {
+        let anteriores: Vec<f64> = self
+            .valores
+            .iter()
+            .map(|valor| f64::from_bits(valor.load(Ordering::Relaxed)))
+            .collect();
+
+        for y in 0..self.grade.altura {
+            for x in 0..self.grade.largura {
+                let local = Ponto { x, y };
+                let indice = (y * self.grade.largura + x) as usize;
+
+                let vizinhos: Vec<f64> = self
+                    .grade
+                    .vizinhanca(&local, 1)
+                    .into_iter()
+                    .filter(|(_, distancia)| *distancia == 1)
+                    .filter_map(|(vizinho, _)| self.grade.indice(&vizinho))
+                    .map(|i| anteriores[i])
+                    .collect();
+                let media = if vizinhos.is_empty() {
+                    anteriores[indice]
+                } else {
+                    vizinhos.iter().sum::<f64>() / vizinhos.len() as f64
+                };
+
+                let valor =
+                    ((1.0 - difusao) * anteriores[indice] + difusao * media) * (1.0 - evaporacao);
+                self.valores[indice].store(valor.to_bits(), Ordering::Relaxed);
+            }
+        }
+    }
+}
@@ -0,0 +1,720 @@
+use super::configuracao::{Configuracao, ParametrosPasso};
+use super::densidade::{distancia_entre_par_de_dados, similaridade};
+use super::estagnacao::{resolver_estagnacao, RegraEstagnacao};
+use super::grade::Grade;
+use super::grao::Grao;
+use super::mapa::{Mapa, Vizinhanca};
+use super::modelos::{modelo_por_nome, ModeloProbabilidade};
+use super::movimento::Movimento;
+use super::outros::Ponto;
+use super::populacao::dividir_formigas;
+use rand::Rng;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::vec;
+use uuid::Uuid;
+
+/// Ação escolhida por uma formiga num passo
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Acao {
+    Nenhuma,
+    Pegar(Uuid),
+    Largar,
+}
+
+/// Movimento e ação propostos por uma formiga numa rodada do modo síncrono
+#[derive(Clone, Copy, Debug)]
+pub struct Proposta {
+    pub posicao: Ponto,
+    pub direcao: usize,
+    pub acao: Acao,
+    /// A formiga estava carregada numa célula que aceita o grão e sorteou se largava
+    pub tentou_largar: bool,
+    /// Passo da execução em que a proposta foi feita
+    pub passo: i64,
+}
+
+/// Parâmetros próprios de uma formiga; os ausentes seguem a configuração
+#[derive(Clone, Debug, Default)]
+pub struct Perfil {
+    /// Índice da população da formiga, para os relatórios
+    pub populacao: usize,
+    /// Quantas vezes o movimento de cada passo é aplicado; `None` é 1
+    pub velocidade: Option<i32>,
+    pub raio_vizinhanca: Option<i32>,
+    pub modelo: Option<Arc<dyn ModeloProbabilidade>>,
+}
+
+/// Estado de uma formiga. As formigas não têm thread própria: são executadas em lotes
+/// pelas threads de trabalho do cenário.
+#[derive(Clone, Debug)]
+pub struct Formiga {
+    pub id: Uuid,
+    pub posicao: Ponto,
+    /// Direção do último movimento, usada pela caminhada persistente
+    pub ultima_direcao: Option<usize>,
+    pub segurando_objeto: Option<Grao>,
+    /// Escala das distâncias na função de densidade; muda com o tempo se o alpha adaptativo
+    /// estiver ligado
+    pub alpha: f64,
+    pub perfil: Perfil,
+    /// Passos dados, grãos pegos e grãos largados desde o início
+    pub passos: u64,
+    pub pegadas: u64,
+    pub largadas: u64,
+    /// Vezes em que a regra de estagnação agiu
+    pub estagnacoes: u64,
+    /// Tentativas seguidas de largar que falharam
+    falhas_seguidas: u32,
+    /// Tentativas de largar e falhas desde o último ajuste do alpha
+    tentativas_largar: u32,
+    falhas_largar: u32,
+    passos_desde_ajuste: u32,
+    /// Últimos grãos largados pela formiga: onde ficaram e os seus dados
+    memoria: VecDeque<(Ponto, Vec<f64>)>,
+    /// Célula lembrada para onde a formiga carregada está indo
+    destino: Option<Ponto>,
+}
+
+impl Formiga {
+    pub fn new(ponto_surgimento: Ponto, alpha: f64, perfil: Perfil) -> Formiga {
+        Formiga {
+            id: Uuid::new_v4(),
+            posicao: ponto_surgimento,
+            ultima_direcao: None,
+            segurando_objeto: None,
+            alpha,
+            perfil,
+            passos: 0,
+            pegadas: 0,
+            largadas: 0,
+            estagnacoes: 0,
+            falhas_seguidas: 0,
+            tentativas_largar: 0,
+            falhas_largar: 0,
+            passos_desde_ajuste: 0,
+            memoria: VecDeque::new(),
+            destino: None,
+        }
+    }
+
+    /// Executa um passo da formiga: move e tenta pegar ou largar um grão. `passo_atual` é
+    /// o passo da execução, usado para avaliar as agendas de parâmetros.
+    pub fn passo(&mut self, mapa: &Mapa, configuracao: &Configuracao, passo_atual: i64) {
+        let parametros = configuracao.parametros_no_passo(passo_atual, self.alpha, &self.perfil);
+
+        // Movendo a formiga
+        let (posicao, direcao) = self.proxima_posicao(mapa, configuracao, &parametros);
+        self.posicao = mapa.mover_formiga(&self.posicao, posicao);
+        self.ultima_direcao = Some(direcao);
+        let segurava = self.segurando_objeto.is_some();
+
+        // Ações relacionadas a ter ou não itens na mão
+        let tentou_largar = acao_segurar_objeto(
+            &self.posicao,
+            &mut self.segurando_objeto,
+            mapa,
+            configuracao,
+            &parametros,
+            true,
+        )
+        .is_some();
+
+        let largada = self
+            .verificar_estagnacao(tentou_largar, mapa, configuracao, passo_atual)
+            .unwrap_or(self.posicao);
+        self.atualizar_memoria(segurava, &largada, mapa, configuracao);
+        self.registrar_passo(segurava, tentou_largar, &largada, mapa, configuracao);
+    }
+
+    /// Sorteia o movimento e a ação do próximo passo sem alterar a formiga nem o mapa
+    pub fn propor(&self, mapa: &Mapa, configuracao: &Configuracao, passo_atual: i64) -> Proposta {
+        let parametros = configuracao.parametros_no_passo(passo_atual, self.alpha, &self.perfil);
+        let (posicao, direcao) = self.proxima_posicao(mapa, configuracao, &parametros);
+        let (acao, prob) = decidir_acao(
+            &posicao,
+            &self.segurando_objeto,
+            mapa,
+            configuracao,
+            &parametros,
+            true,
+        );
+
+        Proposta {
+            posicao,
+            direcao,
+            acao,
+            tentou_largar: prob.is_some(),
+            passo: passo_atual,
+        }
+    }
+
+    /// Aplica uma proposta feita com `propor`. Retorna falso se a ação perdeu um conflito,
+    /// inclusive quando a célula de destino já estava ocupada por outra formiga (com a
+    /// regra de uma formiga por célula), caso em que a formiga não sai do lugar.
+    pub fn aplicar(
+        &mut self,
+        proposta: Proposta,
+        mapa: &Mapa,
+        configuracao: &Configuracao,
+    ) -> bool {
+        self.posicao = mapa.mover_formiga(&self.posicao, proposta.posicao);
+        self.ultima_direcao = Some(proposta.direcao);
+        let bloqueada = self.posicao != proposta.posicao;
+        let tentou_largar = proposta.tentou_largar && !bloqueada;
+
+        let segurava = self.segurando_objeto.is_some();
+        let aplicou = !bloqueada
+            && aplicar_acao(
+                &self.posicao,
+                &mut self.segurando_objeto,
+                mapa,
+                proposta.acao,
+            );
+
+        let largada = self
+            .verificar_estagnacao(tentou_largar, mapa, configuracao, proposta.passo)
+            .unwrap_or(self.posicao);
+        self.atualizar_memoria(segurava, &largada, mapa, configuracao);
+        self.registrar_passo(segurava, tentou_largar, &largada, mapa, configuracao);
+
+        aplicou
+    }
+
+    /// Com a memória ligada a formiga carregada anda em direção à célula lembrada; nos
+    /// outros casos ela segue a estratégia de movimento configurada, cujas direções
+    /// sorteadas puxam para o feromônio quando a formiga está sem carga. Fora da ida à
+    /// célula lembrada o movimento é multiplicado pela velocidade do perfil. Retorna a nova
+    /// posição e a direção tomada.
+    fn proxima_posicao(
+        &self,
+        mapa: &Mapa,
+        configuracao: &Configuracao,
+        parametros: &ParametrosPasso,
+    ) -> (Ponto, usize) {
+        let movimento = match self.destino {
+            Some(destino) if self.segurando_objeto.is_some() => {
+                mover_para(&self.posicao, &destino, mapa.grade())
+            }
+            _ => {
+                let movimento = configuracao
+                    .movimento
+                    .mover(self, mapa, configuracao, parametros);
+                Movimento {
+                    passos: movimento.passos * self.perfil.velocidade.unwrap_or(1).max(1),
+                    ..movimento
+                }
+            }
+        };
+
+        // A grade aplica a fronteira caso a formiga saia dos limites
+        (
+            mapa.grade()
+                .mover(&self.posicao, movimento.direcao, movimento.passos),
+            movimento.direcao,
+        )
+    }
+
+    /// Conta as tentativas seguidas de largar que falharam e, ao chegar em
+    /// `limite_estagnacao`, aplica a regra de estagnação configurada. Se ela agiu, retorna
+    /// a célula onde o grão carregado ficou.
+    fn verificar_estagnacao(
+        &mut self,
+        tentou_largar: bool,
+        mapa: &Mapa,
+        configuracao: &Configuracao,
+        passo_atual: i64,
+    ) -> Option<Ponto> {
+        if self.segurando_objeto.is_none() {
+            self.falhas_seguidas = 0;
+            return None;
+        }
+        if !tentou_largar || configuracao.regra_estagnacao == RegraEstagnacao::Nenhuma {
+            return None;
+        }
+
+        self.falhas_seguidas += 1;
+        if self.falhas_seguidas < configuracao.limite_estagnacao {
+            return None;
+        }
+
+        let parametros = configuracao.parametros_no_passo(passo_atual, self.alpha, &self.perfil);
+        let celula = resolver_estagnacao(
+            &self.posicao,
+            &mut self.segurando_objeto,
+            mapa,
+            configuracao,
+            &parametros,
+        );
+        if celula.is_some() {
+            self.estagnacoes += 1;
+        }
+        self.falhas_seguidas = 0;
+
+        celula
+    }
+
+    /// Memória de curto prazo de Lumer e Faieta: ao largar, a formiga guarda a posição e os
+    /// dados do grão (esquecendo os mais antigos além de `tamanho_memoria`); ao pegar, passa
+    /// a ir para a posição lembrada do grão mais parecido com o que carrega. Chegando lá, ou
+    /// largando antes, volta a andar ao acaso. `largada` é a célula onde um grão largado
+    /// neste passo ficou.
+    fn atualizar_memoria(
+        &mut self,
+        segurava: bool,
+        largada: &Ponto,
+        mapa: &Mapa,
+        configuracao: &Configuracao,
+    ) {
+        if configuracao.tamanho_memoria == 0 {
+            return;
+        }
+
+        match (&self.segurando_objeto, segurava) {
+            // Largou: o grão está no topo da célula da largada
+            (None, true) => {
+                self.destino = None;
+                if let Some(grao) = mapa.grao_local(largada) {
+                    self.memoria.push_back((*largada, grao.dados));
+                    while self.memoria.len() > configuracao.tamanho_memoria {
+                        self.memoria.pop_front();
+                    }
+                }
+            }
+            // Pegou: escolhe o destino entre as posições lembradas
+            (Some(grao), false) => {
+                self.destino = self
+                    .memoria
+                    .iter()
+                    .map(|(posicao, dados)| {
+                        (posicao, distancia_entre_par_de_dados(&grao.dados, dados))
+                    })
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|(posicao, _)| *posicao);
+            }
+            _ => {}
+        }
+
+        if self.destino == Some(self.posicao) {
+            self.destino = None;
+        }
+    }
+
+    /// Conta passos, grãos pegos e largados e as tentativas de largar; cada grão largado
+    /// deixa feromônio na célula `largada`, onde ele ficou. Com o alpha adaptativo ligado, ajusta o alpha a cada
+    /// `periodo_alpha` passos como no ATTA de Handl et al.: se a fração de tentativas que
+    /// falharam passou da taxa alvo o alpha cresce, facilitando as largadas; senão diminui.
+    fn registrar_passo(
+        &mut self,
+        segurava: bool,
+        tentou_largar: bool,
+        largada: &Ponto,
+        mapa: &Mapa,
+        configuracao: &Configuracao,
+    ) {
+        self.passos += 1;
+        match (segurava, self.segurando_objeto.is_some()) {
+            (false, true) => self.pegadas += 1,
+            (true, false) => self.registrar_largada(largada, mapa, configuracao),
+            _ => {}
+        }
+
+        if tentou_largar {
+            self.tentativas_largar += 1;
+            if self.segurando_objeto.is_some() {
+                self.falhas_largar += 1;
+            }
+        }
+
+        if !configuracao.alpha_adaptativo {
+            return;
+        }
+
+        self.passos_desde_ajuste += 1;
+        if self.passos_desde_ajuste < configuracao.periodo_alpha {
+            return;
+        }
+
+        if self.tentativas_largar > 0 {
+            let taxa_falha = self.falhas_largar as f64 / self.tentativas_largar as f64;
+            if taxa_falha > configuracao.taxa_falha_alvo {
+                self.alpha += configuracao.passo_alpha;
+            } else {
+                self.alpha = (self.alpha - configuracao.passo_alpha).max(configuracao.passo_alpha);
+            }
+        }
+
+        self.tentativas_largar = 0;
+        self.falhas_largar = 0;
+        self.passos_desde_ajuste = 0;
+    }
+
+    /// Conta um grão largado em `celula` e deixa feromônio nela
+    fn registrar_largada(&mut self, celula: &Ponto, mapa: &Mapa, configuracao: &Configuracao) {
+        self.largadas += 1;
+        if let Some(campo) = mapa.feromonio() {
+            campo.depositar(celula, configuracao.deposito_feromonio);
+        }
+    }
+
+    /// Fase de finalização: a formiga continua andando com a coleta desativada até
+    /// largar o grão que segura. Se os passos acabarem antes, o grão é largado na
+    /// célula livre de maior `pd` por onde ela passou. As agendas ficam paradas no valor
+    /// de `passo_atual`, o fim da execução. As largadas contam como as dos passos comuns.
+    pub fn finalizar(
+        &mut self,
+        mapa: &Mapa,
+        configuracao: &Configuracao,
+        passos: i64,
+        passo_atual: i64,
+    ) {
+        let mut melhor_celula: Option<(Ponto, f64)> = None;
+        let parametros = configuracao.parametros_no_passo(passo_atual, self.alpha, &self.perfil);
+
+        for _ in 0..passos {
+            if self.segurando_objeto.is_none() {
+                return;
+            }
+
+            // Movendo a formiga
+            let (posicao, direcao) = self.proxima_posicao(mapa, configuracao, &parametros);
+            self.posicao = mapa.mover_formiga(&self.posicao, posicao);
+            self.ultima_direcao = Some(direcao);
+            if self.destino == Some(self.posicao) {
+                self.destino = None;
+            }
+
+            // Tenta largar, guardando a melhor célula livre vista até aqui
+            if let Some(prob) = acao_segurar_objeto(
+                &self.posicao,
+                &mut self.segurando_objeto,
+                mapa,
+                configuracao,
+                &parametros,
+                false,
+            ) {
+                if self.segurando_objeto.is_none() {
+                    let posicao = self.posicao;
+                    self.registrar_largada(&posicao, mapa, configuracao);
+                    return;
+                }
+                if melhor_celula.is_none_or(|(_, melhor)| prob > melhor) {
+                    melhor_celula = Some((self.posicao, prob));
+                }
+            }
+        }
+
+        // Passos esgotados: larga na melhor célula livre encontrada
+        if let Some(grao) = self.segurando_objeto.take() {
+            let celula = self.largar_fora(mapa, grao, melhor_celula.map(|(celula, _)| celula));
+            self.registrar_largada(&celula, mapa, configuracao);
+        }
+    }
+
+    /// Larga o grão na célula `preferida`, se ela estiver livre, ou na livre mais próxima
+    /// da formiga; com o mapa cheio, empilha no próprio local. Retorna onde ele ficou.
+    fn largar_fora(&self, mapa: &Mapa, grao: Grao, preferida: Option<Ponto>) -> Ponto {
+        let grao = match preferida {
+            Some(celula) => match mapa.largar(&celula, grao) {
+                Ok(()) => return celula,
+                Err(grao) => grao,
+            },
+            None => grao,
+        };
+
+        if let Some(celula) = mapa.celula_livre_proxima(&self.posicao) {
+            match mapa.largar(&celula, grao) {
+                Ok(()) => return celula,
+                Err(grao) => mapa.empilhar(&self.posicao, grao),
+            }
+        } else {
+            mapa.empilhar(&self.posicao, grao);
+        }
+
+        self.posicao
+    }
+}
+
+/// Anda uma célula na direção que mais aproxima a formiga de `destino`
+fn mover_para(posicao: &Ponto, destino: &Ponto, grade: &Grade) -> Movimento {
+    let direcao = (0..grade.num_direcoes())
+        .min_by_key(|direcao| grade.distancia(&grade.mover(posicao, *direcao, 1), destino))
+        .unwrap_or(0);
+
+    Movimento { direcao, passos: 1 }
+}
+
+/// Cria `numero` formigas em posições aleatórias. Com populações configuradas as formigas
+/// são divididas entre elas conforme os pesos, e cada uma recebe os parâmetros da sua
+/// população, com o alpha sorteado no intervalo dado.
+pub fn gerar_formigas(numero: i32, configuracao: &Configuracao) -> Vec<Formiga> {
+    let mut rng = rand::thread_rng();
+    let mut formigas: Vec<Formiga> = vec![];
+
+    if configuracao.populacoes.is_empty() {
+        for _ in 0..numero {
+            formigas.push(Formiga::new(
+                configuracao.grade.ponto_aleatorio(&mut rng),
+                configuracao.alpha,
+                Perfil::default(),
+            ));
+        }
+
+        return formigas;
+    }
+
+    let quantidades = dividir_formigas(&configuracao.populacoes, numero);
+    for (indice, (populacao, quantidade)) in
+        configuracao.populacoes.iter().zip(quantidades).enumerate()
+    {
+        let perfil = Perfil {
+            populacao: indice,
+            velocidade: populacao.velocidade,
+            raio_vizinhanca: populacao.raio_vizinhanca,
+            // O nome já foi validado na leitura do cenário
+            modelo: populacao
+                .modelo
+                .as_ref()
+                .and_then(|nome| modelo_por_nome(nome, configuracao.inclinacao_sigmoide).ok()),
+        };
+
+        for _ in 0..quantidade {
+            let alpha = match populacao.alpha {
+                Some((minimo, maximo)) if minimo < maximo => rng.gen_range(minimo..=maximo),
+                Some((minimo, _)) => minimo,
+                None => configuracao.alpha,
+            };
+
+            formigas.push(Formiga::new(
+                configuracao.grade.ponto_aleatorio(&mut rng),
+                alpha,
+                perfil.clone(),
+            ));
+        }
+    }
+
+    formigas
+}
+
+/// Pega ou larga um grão na posição da formiga. Com `pegar` falso a formiga só pode largar.
+/// Retorna o `pd` avaliado quando a formiga carregada está numa célula que aceita o grão.
+///
+/// As probabilidades são calculadas sobre uma cópia da vizinhança; a troca do grão só
+/// acontece com o lock da célula, e falha se outra formiga chegou antes.
+fn acao_segurar_objeto(
+    posicao_formiga: &Ponto,
+    objeto: &mut Option<Grao>,
+    mapa: &Mapa,
+    configuracao: &Configuracao,
+    parametros: &ParametrosPasso,
+    pegar: bool,
+) -> Option<f64> {
+    let (acao, prob) = decidir_acao(
+        posicao_formiga,
+        objeto,
+        mapa,
+        configuracao,
+        parametros,
+        pegar,
+    );
+    aplicar_acao(posicao_formiga, objeto, mapa, acao);
+
+    prob
+}
+
+/// Sorteia a ação da formiga sem alterar o mapa
+fn decidir_acao(
+    posicao_formiga: &Ponto,
+    objeto: &Option<Grao>,
+    mapa: &Mapa,
+    configuracao: &Configuracao,
+    parametros: &ParametrosPasso,
+    pegar: bool,
+) -> (Acao, Option<f64>) {
+    let mut rng: rand::prelude::ThreadRng = rand::thread_rng();
+    let numero_aleatorio: f64 = rng.gen_range(0.0..=1.0);
+
+    // Operações
+    if let Some(mao) = objeto {
+        // Tem algo na mão que pode largar
+        if mapa.aceita_grao(posicao_formiga) {
+            // Procurar pelos grãos ao redor
+            let graos_perto = graos_redor(posicao_formiga, mao, mapa, configuracao, parametros);
+
+            let prob = pd(mao, &graos_perto, configuracao, parametros);
+            let acao = if numero_aleatorio <= prob {
+                Acao::Largar
+            } else {
+                Acao::Nenhuma
+            };
+            return (acao, Some(prob));
+        }
+    } else if !pegar {
+        // Coleta desativada
+    } else if let Some(grao) = mapa.grao_local(posicao_formiga) {
+        // Não tem nada na mão mas tem algo na localização que pode pegar
+        let graos_perto = graos_redor(posicao_formiga, &grao, mapa, configuracao, parametros);
+
+        if numero_aleatorio <= pp(&grao, &graos_perto, configuracao, parametros) {
+            return (Acao::Pegar(grao.id), None);
+        }
+    }
+
+    (Acao::Nenhuma, None)
+}
+
+/// Executa a ação no mapa. Retorna falso se ela não pôde ser feita porque outra
+/// formiga pegou o grão ou ocupou a célula antes.
+fn aplicar_acao(
+    posicao_formiga: &Ponto,
+    objeto: &mut Option<Grao>,
+    mapa: &Mapa,
+    acao: Acao,
+) -> bool {
+    match acao {
+        Acao::Nenhuma => true,
+        Acao::Pegar(id) => {
+            // Removendo do mapa, se ainda estiver lá
+            *objeto = mapa.pegar(posicao_formiga, id);
+            objeto.is_some()
+        }
+        Acao::Largar => match objeto.take().map(|mao| mapa.largar(posicao_formiga, mao)) {
+            Some(Ok(())) => true,
+            Some(Err(mao)) => {
+                // Outra formiga largou primeiro
+                *objeto = Some(mao);
+                false
+            }
+            None => false,
+        },
+    }
+}
+
+/// Grãos da vizinhança configurada, com o peso de cada um, vistos pelo grão `grao`
+fn graos_redor(
+    local: &Ponto,
+    grao: &Grao,
+    mapa: &Mapa,
+    configuracao: &Configuracao,
+    parametros: &ParametrosPasso,
+) -> Vizinhanca {
+    mapa.graos_redor(
+        local,
+        parametros.raio_vizinhanca,
+        configuracao.formato_vizinhanca,
+        configuracao.sigma_vizinhanca,
+        Some(grao.id),
+    )
+}
+
+fn pp(
+    grao: &Grao,
+    graos_perto: &Vizinhanca,
+    configuracao: &Configuracao,
+    parametros: &ParametrosPasso,
+) -> f64 {
+    let f = similaridade(grao, graos_perto, configuracao, parametros);
+    parametros.modelo.pp(f, &parametros.limiares)
+}
+
+fn pd(
+    grao: &Grao,
+    graos_perto: &Vizinhanca,
+    configuracao: &Configuracao,
+    parametros: &ParametrosPasso,
+) -> f64 {
+    let f = similaridade(grao, graos_perto, configuracao, parametros);
+    parametros.modelo.pd(f, &parametros.limiares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::grade::{Fronteira, Topologia};
+
+    fn grao(x: i32, y: i32) -> Grao {
+        Grao::new(Ponto { x, y }, vec![x as f64 / 10.0, y as f64 / 10.0], 0)
+    }
+
+    #[test]
+    fn memoria_leva_pelo_lado_mais_curto_da_grade_toroidal() {
+        for topologia in [Topologia::Quadrada, Topologia::Hexagonal] {
+            let grade = Grade::new(64, 64, Fronteira::Toroidal, topologia);
+            let mut posicao = Ponto { x: 62, y: 10 };
+            let destino = Ponto { x: 1, y: 10 };
+
+            for _ in 0..3 {
+                let movimento = mover_para(&posicao, &destino, &grade);
+                posicao = grade.mover(&posicao, movimento.direcao, movimento.passos);
+            }
+            assert_eq!(posicao, destino);
+        }
+    }
+
+    #[test]
+    fn estagnacao_registra_a_celula_onde_o_grao_ficou() {
+        let grade = Grade::new(5, 5, Fronteira::Toroidal, Topologia::Quadrada);
+        let centro = Ponto { x: 2, y: 2 };
+        let mut mapa = Mapa::new(grade, vec![grao(2, 2)]);
+        mapa.ativar_feromonio();
+        let configuracao = Configuracao {
+            grade,
+            regra_estagnacao: RegraEstagnacao::LargarForcado,
+            limite_estagnacao: 1,
+            tamanho_memoria: 5,
+            ..Configuracao::default()
+        };
+
+        // Carregada, parada na célula ocupada e sem conseguir largar
+        let mut formiga = Formiga::new(centro, 1.0, Perfil::default());
+        formiga.segurando_objeto = Some(grao(4, 4));
+        let proposta = Proposta {
+            posicao: centro,
+            direcao: 0,
+            acao: Acao::Nenhuma,
+            tentou_largar: true,
+            passo: 0,
+        };
+        formiga.aplicar(proposta, &mapa, &configuracao);
+
+        assert!(formiga.segurando_objeto.is_none());
+        assert_eq!(formiga.largadas, 1);
+        let (largada, _) = formiga.memoria[0];
+        assert_ne!(largada, centro);
+        assert_eq!(grade.distancia(&largada, &centro), 1);
+        assert_eq!(mapa.grao_local(&largada).unwrap().dados, vec![0.4, 0.4]);
+
+        let campo = mapa.feromonio().unwrap();
+        assert_eq!(campo.valor(&largada), configuracao.deposito_feromonio);
+        assert_eq!(campo.valor(&centro), 0.0);
+    }
+
+    #[test]
+    fn finalizar_larga_todos_os_graos() {
+        let grade = Grade::new(6, 6, Fronteira::Toroidal, Topologia::Quadrada);
+        // Grade quase cheia: sobram 4 células para as 4 formigas carregadas
+        let graos: Vec<Grao> = (0..32).map(|i| grao(i % 6, i / 6)).collect();
+        let mapa = Mapa::new(grade, graos);
+        let configuracao = Configuracao {
+            grade,
+            ..Configuracao::default()
+        };
+
+        let mut formigas: Vec<Formiga> = (0..4)
+            .map(|i| {
+                let mut formiga = Formiga::new(Ponto { x: i, y: 0 }, 1.0, Perfil::default());
+                formiga.segurando_objeto = Some(grao(i, 5));
+                formiga
+            })
+            .collect();
+        for (passos, formiga) in [0, 1, 50, 50].into_iter().zip(&mut formigas) {
+            formiga.finalizar(&mapa, &configuracao, passos, 0);
+        }
+
+        assert!(formigas.iter().all(|f| f.segurando_objeto.is_none()));
+        assert!(formigas.iter().all(|f| f.largadas == 1));
+        let graos = mapa.graos();
+        assert_eq!(graos.len(), 36);
+        assert!(mapa.violacoes(&[]).is_empty());
+    }
+}
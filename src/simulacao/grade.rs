@@ -0,0 +1,446 @@
+use super::outros::Ponto;
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+/// O que acontece com quem tenta sair da grade
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Fronteira {
+    /// As bordas opostas se tocam: quem sai por um lado entra pelo outro
+    Toroidal,
+    /// Quem passa da borda volta para dentro como num espelho
+    Refletora,
+    /// Paredes: quem passa da borda fica parado nela
+    Limitada,
+}
+
+impl FromStr for Fronteira {
+    type Err = String;
+
+    fn from_str(valor: &str) -> Result<Self, Self::Err> {
+        match valor {
+            "toroidal" => Ok(Fronteira::Toroidal),
+            "refletora" => Ok(Fronteira::Refletora),
+            "limitada" => Ok(Fronteira::Limitada),
+            _ => Err(format!("fronteira desconhecida `{}`", valor)),
+        }
+    }
+}
+
+impl fmt::Display for Fronteira {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nome = match self {
+            Fronteira::Toroidal => "toroidal",
+            Fronteira::Refletora => "refletora",
+            Fronteira::Limitada => "limitada",
+        };
+        write!(f, "{}", nome)
+    }
+}
+
+/// Forma das células e de como elas se ligam
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Topologia {
+    /// Células quadradas com 8 vizinhas (incluindo as diagonais)
+    Quadrada,
+    /// Células hexagonais com 6 vizinhas. As posições continuam guardadas como (coluna,
+    /// linha), com as linhas ímpares deslocadas meia célula para a direita; movimento e
+    /// vizinhança são calculados em coordenadas axiais.
+    Hexagonal,
+}
+
+impl FromStr for Topologia {
+    type Err = String;
+
+    fn from_str(valor: &str) -> Result<Self, Self::Err> {
+        match valor {
+            "quadrada" => Ok(Topologia::Quadrada),
+            "hexagonal" => Ok(Topologia::Hexagonal),
+            _ => Err(format!("topologia desconhecida `{}`", valor)),
+        }
+    }
+}
+
+impl fmt::Display for Topologia {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nome = match self {
+            Topologia::Quadrada => "quadrada",
+            Topologia::Hexagonal => "hexagonal",
+        };
+        write!(f, "{}", nome)
+    }
+}
+
+/// Deslocamentos das 8 direções da grade quadrada
+const DIRECOES_QUADRADA: [(i32, i32); 8] = [
+    (0, 1),   // Cima
+    (1, 0),   // Direita
+    (0, -1),  // Baixo
+    (-1, 0),  // Esquerda
+    (1, 1),   // Diagonal superior direita
+    (-1, 1),  // Diagonal superior esquerda
+    (1, -1),  // Diagonal inferior direita
+    (-1, -1), // Diagonal inferior esquerda
+];
+
+/// Deslocamentos axiais (q, r) das 6 direções da grade hexagonal
+const DIRECOES_HEXAGONAL: [(i32, i32); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+
+/// Forma da vizinhança usada para calcular a similaridade local
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FormatoVizinhanca {
+    /// Células a até `raio` passos sem usar diagonais (na grade hexagonal, igual a Moore)
+    VonNeumann,
+    /// Janela quadrada de raio `raio` (na grade hexagonal, os anéis até `raio`)
+    Moore,
+    /// Células cujo centro está a até `raio` do centro da célula da formiga
+    Circular,
+    /// Janela de Moore com pesos gaussianos que diminuem com a distância
+    Gaussiana,
+}
+
+impl FromStr for FormatoVizinhanca {
+    type Err = String;
+
+    fn from_str(valor: &str) -> Result<Self, Self::Err> {
+        match valor {
+            "von_neumann" => Ok(FormatoVizinhanca::VonNeumann),
+            "moore" => Ok(FormatoVizinhanca::Moore),
+            "circular" => Ok(FormatoVizinhanca::Circular),
+            "gaussiana" => Ok(FormatoVizinhanca::Gaussiana),
+            _ => Err(format!("formato de vizinhança desconhecido `{}`", valor)),
+        }
+    }
+}
+
+impl fmt::Display for FormatoVizinhanca {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nome = match self {
+            FormatoVizinhanca::VonNeumann => "von_neumann",
+            FormatoVizinhanca::Moore => "moore",
+            FormatoVizinhanca::Circular => "circular",
+            FormatoVizinhanca::Gaussiana => "gaussiana",
+        };
+        write!(f, "{}", nome)
+    }
+}
+
+/// Distâncias de uma célula vizinha até o centro da vizinhança
+struct Distancias {
+    /// Passos pela grade, andando também na diagonal
+    passos: i32,
+    /// Passos pela grade sem diagonais
+    manhattan: i32,
+    euclidiana: f64,
+}
+
+/// Grade de células compartilhada pelo mapa, pelas formigas e pelos grãos.
+/// As posições válidas vão de `0` a `largura - 1` e de `0` a `altura - 1`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Grade {
+    pub largura: i32,
+    pub altura: i32,
+    pub fronteira: Fronteira,
+    pub topologia: Topologia,
+}
+
+impl Grade {
+    pub fn new(largura: i32, altura: i32, fronteira: Fronteira, topologia: Topologia) -> Grade {
+        Grade {
+            largura,
+            altura,
+            fronteira,
+            topologia,
+        }
+    }
+
+    pub fn num_celulas(&self) -> usize {
+        (self.largura * self.altura) as usize
+    }
+
+    pub fn contem(&self, local: &Ponto) -> bool {
+        local.x >= 0 && local.x < self.largura && local.y >= 0 && local.y < self.altura
+    }
+
+    /// Índice da célula num vetor organizado por linhas
+    pub fn indice(&self, local: &Ponto) -> Option<usize> {
+        if !self.contem(local) {
+            return None;
+        }
+
+        Some((local.y * self.largura + local.x) as usize)
+    }
+
+    /// Traz para dentro da grade uma posição que pode ter saído dela, conforme a fronteira
+    pub fn ajustar(&self, local: Ponto) -> Ponto {
+        Ponto {
+            x: ajustar_coordenada(local.x, self.largura, self.fronteira),
+            y: ajustar_coordenada(local.y, self.altura, self.fronteira),
+        }
+    }
+
+    /// Número de direções em que uma formiga pode andar
+    pub fn num_direcoes(&self) -> usize {
+        match self.topologia {
+            Topologia::Quadrada => DIRECOES_QUADRADA.len(),
+            Topologia::Hexagonal => DIRECOES_HEXAGONAL.len(),
+        }
+    }
+
+    /// Anda `passos` células na direção `direcao` (de `0` a `num_direcoes() - 1`),
+    /// aplicando a fronteira no destino
+    pub fn mover(&self, local: &Ponto, direcao: usize, passos: i32) -> Ponto {
+        let destino = match self.topologia {
+            Topologia::Quadrada => {
+                let (dx, dy) = DIRECOES_QUADRADA[direcao];
+                Ponto {
+                    x: local.x + dx * passos,
+                    y: local.y + dy * passos,
+                }
+            }
+            Topologia::Hexagonal => {
+                let (q, r) = para_axial(local);
+                let (dq, dr) = DIRECOES_HEXAGONAL[direcao];
+                de_axial(q + dq * passos, r + dr * passos)
+            }
+        };
+
+        self.ajustar(destino)
+    }
+
+    /// Células a até `raio` passos de `local`, com a distância de cada uma: uma janela
+    /// quadrada na grade quadrada e anéis hexagonais na hexagonal
+    pub fn vizinhanca(&self, local: &Ponto, raio: i32) -> Vec<(Ponto, i32)> {
+        self.celulas_ao_redor(local, raio)
+            .into_iter()
+            .map(|(vizinho, distancias)| (vizinho, distancias.passos))
+            .collect()
+    }
+
+    /// Células da vizinhança de `local` no formato pedido, com o peso de cada uma no
+    /// cálculo da similaridade. Só o formato gaussiano dá pesos diferentes de 1, que caem
+    /// com a distância euclidiana até o centro conforme o desvio `sigma`.
+    pub fn vizinhanca_ponderada(
+        &self,
+        local: &Ponto,
+        raio: i32,
+        formato: FormatoVizinhanca,
+        sigma: f64,
+    ) -> Vec<(Ponto, f64)> {
+        self.celulas_ao_redor(local, raio)
+            .into_iter()
+            .filter_map(|(vizinho, distancias)| {
+                let peso = match formato {
+                    FormatoVizinhanca::VonNeumann if distancias.manhattan > raio => return None,
+                    FormatoVizinhanca::Circular if distancias.euclidiana > raio as f64 => {
+                        return None
+                    }
+                    FormatoVizinhanca::Gaussiana => {
+                        (-distancias.euclidiana.powi(2) / (2.0 * sigma.powi(2))).exp()
+                    }
+                    _ => 1.0,
+                };
+
+                Some((vizinho, peso))
+            })
+            .collect()
+    }
+
+    /// Todas as células a até `raio` passos de `local`, com as suas distâncias ao centro
+    fn celulas_ao_redor(&self, local: &Ponto, raio: i32) -> Vec<(Ponto, Distancias)> {
+        let mut resultado: Vec<(Ponto, Distancias)> = vec![];
+
+        match self.topologia {
+            Topologia::Quadrada => {
+                for dy in -raio..=raio {
+                    for dx in -raio..=raio {
+                        if let Some(vizinho) = self.vizinho(local, dx, dy) {
+                            let distancias = Distancias {
+                                passos: dx.abs().max(dy.abs()),
+                                manhattan: dx.abs() + dy.abs(),
+                                euclidiana: ((dx * dx + dy * dy) as f64).sqrt(),
+                            };
+                            resultado.push((vizinho, distancias));
+                        }
+                    }
+                }
+            }
+            Topologia::Hexagonal => {
+                let (q, r) = para_axial(local);
+                for dq in -raio..=raio {
+                    for dr in (-raio).max(-dq - raio)..=raio.min(-dq + raio) {
+                        let deslocado = de_axial(q + dq, r + dr);
+                        let (dx, dy) = (deslocado.x - local.x, deslocado.y - local.y);
+                        if let Some(vizinho) = self.vizinho(local, dx, dy) {
+                            // Na grade hexagonal não há diagonais: os passos já são a
+                            // menor distância pela grade
+                            let passos = (dq.abs() + dr.abs() + (dq + dr).abs()) / 2;
+
+                            // Centro do hexágono no plano, com vizinhos a distância 1
+                            let x = dq as f64 + dr as f64 / 2.0;
+                            let y = dr as f64 * 3f64.sqrt() / 2.0;
+
+                            let distancias = Distancias {
+                                passos,
+                                manhattan: passos,
+                                euclidiana: (x * x + y * y).sqrt(),
+                            };
+                            resultado.push((vizinho, distancias));
+                        }
+                    }
+                }
+            }
+        }
+
+        // Na grade toroidal um raio do tamanho da grade dá a volta e repete células; cada
+        // uma deve contar uma vez só, com a menor distância. Na hexagonal a janela é até
+        // 1,5 vez mais larga, por causa do deslocamento das linhas.
+        let lado = self.largura.min(self.altura);
+        if self.fronteira == Fronteira::Toroidal && 3 * raio + 1 > lado {
+            let mut unicas: Vec<(Ponto, Distancias)> = vec![];
+            for (vizinho, distancias) in resultado {
+                match unicas.iter_mut().find(|(celula, _)| *celula == vizinho) {
+                    Some(existente) if distancias.passos < existente.1.passos => {
+                        existente.1 = distancias
+                    }
+                    Some(_) => {}
+                    None => unicas.push((vizinho, distancias)),
+                }
+            }
+            resultado = unicas;
+        }
+
+        resultado
+    }
+
+    /// Distância em passos entre duas células. Na grade toroidal é a do caminho mais curto,
+    /// que pode dar a volta pelas bordas.
+    pub fn distancia(&self, a: &Ponto, b: &Ponto) -> i32 {
+        if self.fronteira != Fronteira::Toroidal {
+            return self.distancia_direta(a, b);
+        }
+
+        // Cópias de `b` deslocadas de uma grade inteira em cada direção; na hexagonal a
+        // altura é par, então as linhas deslocadas mantêm a paridade
+        let mut menor = i32::MAX;
+        for dy in [-self.altura, 0, self.altura] {
+            for dx in [-self.largura, 0, self.largura] {
+                let copia = Ponto {
+                    x: b.x + dx,
+                    y: b.y + dy,
+                };
+                menor = menor.min(self.distancia_direta(a, &copia));
+            }
+        }
+
+        menor
+    }
+
+    /// Distância em passos entre duas células, sem considerar a volta pelas bordas
+    fn distancia_direta(&self, a: &Ponto, b: &Ponto) -> i32 {
+        match self.topologia {
+            Topologia::Quadrada => (a.x - b.x).abs().max((a.y - b.y).abs()),
+            Topologia::Hexagonal => {
+                let (qa, ra) = para_axial(a);
+                let (qb, rb) = para_axial(b);
+                let (dq, dr) = (qa - qb, ra - rb);
+                (dq.abs() + dr.abs() + (dq + dr).abs()) / 2
+            }
+        }
+    }
+
+    /// Célula deslocada de `local` por `(dx, dy)` para fins de vizinhança. Na grade toroidal
+    /// a vizinhança dá a volta nas bordas; nas outras, as células de fora não existem.
+    fn vizinho(&self, local: &Ponto, dx: i32, dy: i32) -> Option<Ponto> {
+        let vizinho = Ponto {
+            x: local.x + dx,
+            y: local.y + dy,
+        };
+
+        match self.fronteira {
+            Fronteira::Toroidal => Some(self.ajustar(vizinho)),
+            Fronteira::Refletora | Fronteira::Limitada => Some(vizinho).filter(|v| self.contem(v)),
+        }
+    }
+
+    /// Sorteia uma célula da grade
+    pub fn ponto_aleatorio<R: Rng>(&self, rng: &mut R) -> Ponto {
+        Ponto {
+            x: rng.gen_range(0..self.largura),
+            y: rng.gen_range(0..self.altura),
+        }
+    }
+}
+
+/// Converte (coluna, linha) com linhas ímpares deslocadas para coordenadas axiais (q, r)
+fn para_axial(local: &Ponto) -> (i32, i32) {
+    (local.x - (local.y - (local.y & 1)) / 2, local.y)
+}
+
+fn de_axial(q: i32, r: i32) -> Ponto {
+    Ponto {
+        x: q + (r - (r & 1)) / 2,
+        y: r,
+    }
+}
+
+fn ajustar_coordenada(valor: i32, tamanho: i32, fronteira: Fronteira) -> i32 {
+    if (0..tamanho).contains(&valor) {
+        return valor;
+    }
+
+    match fronteira {
+        Fronteira::Toroidal => valor.rem_euclid(tamanho),
+        Fronteira::Refletora => {
+            // Reflete nas bordas; o período de ida e volta é 2 * (tamanho - 1)
+            if tamanho == 1 {
+                return 0;
+            }
+            let periodo = 2 * (tamanho - 1);
+            let valor = valor.rem_euclid(periodo);
+            if valor < tamanho {
+                valor
+            } else {
+                periodo - valor
+            }
+        }
+        Fronteira::Limitada => valor.clamp(0, tamanho - 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raio_maior_que_a_grade_toroidal_nao_repete_celulas() {
+        for topologia in [Topologia::Quadrada, Topologia::Hexagonal] {
+            let grade = Grade::new(3, 3, Fronteira::Toroidal, topologia);
+            let centro = Ponto { x: 1, y: 1 };
+
+            let vizinhanca = grade.vizinhanca(&centro, 3);
+            assert_eq!(vizinhanca.len(), 9);
+            assert!(vizinhanca.contains(&(centro, 0)));
+
+            let pesos = grade.vizinhanca_ponderada(&centro, 3, FormatoVizinhanca::Moore, 1.0);
+            assert_eq!(pesos.iter().map(|(_, peso)| peso).sum::<f64>(), 9.0);
+        }
+    }
+
+    #[test]
+    fn distancia_toroidal_da_a_volta_nas_bordas() {
+        let canto = Ponto { x: 63, y: 10 };
+        let outro_lado = Ponto { x: 0, y: 10 };
+        for topologia in [Topologia::Quadrada, Topologia::Hexagonal] {
+            let toroidal = Grade::new(64, 64, Fronteira::Toroidal, topologia);
+            assert_eq!(toroidal.distancia(&canto, &outro_lado), 1);
+            assert_eq!(
+                toroidal.distancia(&Ponto { x: 5, y: 0 }, &Ponto { x: 5, y: 62 }),
+                2
+            );
+
+            let limitada = Grade::new(64, 64, Fronteira::Limitada, topologia);
+            assert_eq!(limitada.distancia(&canto, &outro_lado), 63);
+        }
+    }
+}
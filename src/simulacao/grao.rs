@@ -0,0 +1,356 @@
+use std::vec;
+
+use super::grade::Grade;
+use super::outros::Ponto;
+use rand::seq::{index, SliceRandom};
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grao {
+    pub id: Uuid,
+    pub posicao: Ponto,
+    pub dados: Vec<f64>,
+    pub grupo: i32,
+}
+
+impl Grao {
+    pub fn new(posicao: Ponto, dados: Vec<f64>, grupo: i32) -> Grao {
+        Grao {
+            id: Uuid::new_v4(),
+            posicao,
+            dados,
+            grupo,
+        }
+    }
+}
+
+/// Como os grãos são distribuídos na grade no início da execução
+#[derive(Clone, Debug, PartialEq)]
+pub enum Posicionamento {
+    /// Cada grão numa célula sorteada; vários grãos podem cair na mesma célula
+    Aleatorio,
+    /// Células sorteadas sem repetição
+    Unico,
+    /// Reticulado regular cobrindo a grade, com os grãos em ordem embaralhada
+    Reticulado,
+    /// Posições `x y` lidas de um arquivo, uma linha por grão na ordem dos dados
+    Arquivo(String),
+}
+
+impl FromStr for Posicionamento {
+    type Err = String;
+
+    fn from_str(valor: &str) -> Result<Self, Self::Err> {
+        match valor.split_once(char::is_whitespace) {
+            Some(("arquivo", caminho)) => Ok(Posicionamento::Arquivo(caminho.trim().to_string())),
+            _ => match valor {
+                "aleatorio" => Ok(Posicionamento::Aleatorio),
+                "unico" => Ok(Posicionamento::Unico),
+                "reticulado" => Ok(Posicionamento::Reticulado),
+                _ => Err(format!("posicionamento desconhecido `{}`", valor)),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Posicionamento {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Posicionamento::Aleatorio => write!(f, "aleatorio"),
+            Posicionamento::Unico => write!(f, "unico"),
+            Posicionamento::Reticulado => write!(f, "reticulado"),
+            Posicionamento::Arquivo(caminho) => write!(f, "arquivo {}", caminho),
+        }
+    }
+}
+
+/// Os posicionamentos sem colisão precisam de uma célula por grão
+fn verificar_espaco(quantidade: usize, grade: &Grade) -> Result<(), Box<dyn Error>> {
+    if quantidade > grade.num_celulas() {
+        return Err(format!(
+            "{} grãos não cabem em células distintas de uma grade de {} células",
+            quantidade,
+            grade.num_celulas()
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Distribui os grãos na grade conforme o posicionamento
+pub fn posicionar_graos(
+    graos: &mut [Grao],
+    grade: &Grade,
+    posicionamento: &Posicionamento,
+) -> Result<(), Box<dyn Error>> {
+    let mut rng = rand::thread_rng();
+
+    match posicionamento {
+        Posicionamento::Aleatorio => {
+            for grao in graos.iter_mut() {
+                grao.posicao = grade.ponto_aleatorio(&mut rng);
+            }
+        }
+        Posicionamento::Unico => {
+            verificar_espaco(graos.len(), grade)?;
+
+            let celulas = index::sample(&mut rng, grade.num_celulas(), graos.len());
+            for (grao, celula) in graos.iter_mut().zip(celulas) {
+                grao.posicao = Ponto {
+                    x: celula as i32 % grade.largura,
+                    y: celula as i32 / grade.largura,
+                };
+            }
+        }
+        Posicionamento::Reticulado => {
+            verificar_espaco(graos.len(), grade)?;
+            if graos.is_empty() {
+                return Ok(());
+            }
+
+            // Colunas e linhas na proporção da grade, com um grão por ponto do reticulado
+            let n = graos.len() as f64;
+            let colunas = ((n * grade.largura as f64 / grade.altura as f64)
+                .sqrt()
+                .ceil() as usize)
+                .clamp(1, graos.len());
+            let linhas = graos.len().div_ceil(colunas);
+
+            let mut ordem: Vec<usize> = (0..graos.len()).collect();
+            ordem.shuffle(&mut rng);
+            for (ponto, indice) in ordem.into_iter().enumerate() {
+                let (coluna, linha) = (ponto % colunas, ponto / colunas);
+                graos[indice].posicao = Ponto {
+                    x: ((coluna as f64 + 0.5) * grade.largura as f64 / colunas as f64) as i32,
+                    y: ((linha as f64 + 0.5) * grade.altura as f64 / linhas as f64) as i32,
+                };
+            }
+        }
+        Posicionamento::Arquivo(caminho) => {
+            let leitor = io::BufReader::new(File::open(caminho)?);
+            let mut linhas = leitor.lines();
+
+            for (numero, grao) in graos.iter_mut().enumerate() {
+                let linha = linhas.next().ok_or_else(|| {
+                    format!("{}: faltam posições para {} grãos", caminho, numero + 1)
+                })??;
+                let valores: Vec<i32> = linha
+                    .split_whitespace()
+                    .map(|valor| valor.parse::<i32>())
+                    .collect::<Result<Vec<i32>, _>>()?;
+
+                match valores.as_slice() {
+                    [x, y] if grade.contem(&Ponto { x: *x, y: *y }) => {
+                        grao.posicao = Ponto { x: *x, y: *y }
+                    }
+                    _ => {
+                        return Err(format!(
+                            "{}: posição inválida na linha {}",
+                            caminho,
+                            numero + 1
+                        )
+                        .into())
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Não usada pelo binário, que lê os grãos de arquivo
+#[allow(dead_code)]
+pub fn gerar_graos(numero: i32, grade: &Grade, dados: Vec<f64>, grupo: i32) -> Vec<Grao> {
+    let mut graos: Vec<Grao> = vec![];
+
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..numero {
+        graos.push(Grao::new(
+            grade.ponto_aleatorio(&mut rng),
+            dados.clone(),
+            grupo,
+        ));
+    }
+
+    graos
+}
+
+// Alternativa a `ler_graos_de_arquivo` que normaliza os dados; não usada pelo binário
+#[allow(dead_code)]
+pub fn ler_graos_de_arquivo_normalizado(
+    caminho: &str,
+    grade: &Grade,
+) -> Result<Vec<Grao>, Box<dyn Error>> {
+    // Abre o arquivo
+    let arquivo = File::open(caminho)?;
+
+    // Usa um buffer para ler o arquivo linha por linha
+    let leitor = io::BufReader::new(arquivo);
+
+    // Vetor para armazenar todas as linhas de dados
+    let mut todas_linhas: Vec<Vec<f64>> = Vec::new();
+
+    // Itera sobre as linhas do arquivo
+    for linha in leitor.lines() {
+        let linha = linha?;
+        let valores: Vec<&str> = linha.split_whitespace().collect();
+
+        // Transforma cada valor da linha em f64
+        let mut dados: Vec<f64> = valores
+            .iter()
+            .map(|&valor| valor.replace(",", ".").parse::<f64>())
+            .collect::<Result<Vec<f64>, _>>()?;
+
+        // Realiza qualquer troca necessária, se aplicável (no seu caso, troca o índice 0 com o 2)
+        dados.swap(0, 2);
+
+        // Adiciona a linha processada ao vetor
+        todas_linhas.push(dados);
+    }
+
+    // Vetores para armazenar os valores mínimos e máximos de cada coluna
+    let num_colunas = todas_linhas[0].len();
+    let mut min_vals = vec![f64::MAX; num_colunas];
+    let mut max_vals = vec![f64::MIN; num_colunas];
+
+    // Calcula os valores mínimos e máximos de cada coluna
+    for linha in &todas_linhas {
+        for i in 0..num_colunas {
+            if linha[i] < min_vals[i] {
+                min_vals[i] = linha[i];
+            }
+            if linha[i] > max_vals[i] {
+                max_vals[i] = linha[i];
+            }
+        }
+    }
+
+    // Vetor para armazenar os grãos
+    let mut graos: Vec<Grao> = Vec::new();
+    let mut rng = rand::thread_rng();
+
+    // Normaliza os dados e cria os grãos com posições aleatórias
+    for mut dados in todas_linhas {
+        let grupo: i32 = dados[0] as i32;
+        dados.remove(0);
+
+        let dados_normalizados: Vec<f64> = dados
+            .iter()
+            .enumerate()
+            .map(|(i, &valor)| {
+                if max_vals[i] - min_vals[i] == 0.0 {
+                    // Evita divisão por zero caso todos os valores da coluna sejam iguais
+                    0.0
+                } else {
+                    (valor - min_vals[i]) / (max_vals[i] - min_vals[i])
+                }
+            })
+            .collect();
+
+        // Gera uma posição aleatória dentro da grade
+        let posicao = grade.ponto_aleatorio(&mut rng);
+
+        // Cria o grão com a posição aleatória e os dados normalizados
+        let grao = Grao::new(posicao, dados_normalizados, grupo);
+
+        // Adiciona o grão ao vetor
+        graos.push(grao);
+    }
+
+    // Retorna o vetor de grãos
+    Ok(graos)
+}
+
+pub fn ler_graos_de_arquivo(caminho: &str, grade: &Grade) -> Result<Vec<Grao>, Box<dyn Error>> {
+    // Abre o arquivo
+    let arquivo = File::open(caminho)?;
+
+    // Usa um buffer para ler o arquivo linha por linha
+    let leitor = io::BufReader::new(arquivo);
+
+    // Vetor para armazenar todas as linhas de dados
+    let mut todas_linhas: Vec<Vec<f64>> = Vec::new();
+
+    // Itera sobre as linhas do arquivo
+    for linha in leitor.lines() {
+        let linha = linha?;
+        let valores: Vec<&str> = linha.split_whitespace().collect();
+
+        // Transforma cada valor da linha em f64
+        let mut dados: Vec<f64> = valores
+            .iter()
+            .map(|&valor| valor.replace(",", ".").parse::<f64>())
+            .collect::<Result<Vec<f64>, _>>()?;
+
+        // Realiza qualquer troca necessária, se aplicável (no seu caso, troca o índice 0 com o 2)
+        dados.swap(0, 2);
+
+        // Adiciona a linha processada ao vetor
+        todas_linhas.push(dados);
+    }
+
+    // Vetor para armazenar os grãos
+    let mut graos: Vec<Grao> = Vec::new();
+    let mut rng = rand::thread_rng();
+
+    // Cria os grãos com posições aleatórias (sem normalizar os dados)
+    for mut dados in todas_linhas {
+        let grupo: i32 = dados[0] as i32;
+        dados.remove(0);
+
+        // Gera uma posição aleatória dentro da grade
+        let posicao = grade.ponto_aleatorio(&mut rng);
+
+        // Cria o grão com a posição aleatória e os dados sem normalização
+        let grao = Grao::new(posicao, dados, grupo);
+
+        // Adiciona o grão ao vetor
+        graos.push(grao);
+    }
+
+    // Retorna o vetor de grãos
+    Ok(graos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::grade::{Fronteira, Topologia};
+
+    fn graos(quantidade: i32, grade: &Grade) -> Vec<Grao> {
+        gerar_graos(quantidade, grade, vec![0.0], 1)
+    }
+
+    #[test]
+    fn posicionamentos_sem_colisao_rejeitam_grade_pequena() {
+        let grade = Grade::new(3, 3, Fronteira::Toroidal, Topologia::Quadrada);
+        for posicionamento in [Posicionamento::Unico, Posicionamento::Reticulado] {
+            let mut graos = graos(10, &grade);
+            assert!(posicionar_graos(&mut graos, &grade, &posicionamento).is_err());
+        }
+    }
+
+    #[test]
+    fn reticulado_usa_celulas_distintas() {
+        for (largura, altura, quantidade) in [(3, 3, 9), (10, 4, 37), (5, 20, 100), (64, 64, 600)] {
+            let grade = Grade::new(largura, altura, Fronteira::Toroidal, Topologia::Quadrada);
+            let mut graos = graos(quantidade, &grade);
+            posicionar_graos(&mut graos, &grade, &Posicionamento::Reticulado).unwrap();
+
+            let mut celulas: Vec<(i32, i32)> =
+                graos.iter().map(|g| (g.posicao.x, g.posicao.y)).collect();
+            celulas.sort();
+            celulas.dedup();
+            assert_eq!(celulas.len(), quantidade as usize);
+            assert!(graos.iter().all(|g| grade.contem(&g.posicao)));
+        }
+    }
+}
@@ -0,0 +1,385 @@
+use super::feromonio::CampoFeromonio;
+use super::grade::{FormatoVizinhanca, Grade};
+use super::grao::Grao;
+use super::outros::Ponto;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Grãos de uma vizinhança, cada um com o peso da sua célula
+#[derive(Clone, Debug)]
+pub struct Vizinhanca {
+    pub graos: Vec<(Grao, f64)>,
+    /// Soma dos pesos de todas as células da vizinhança, ocupadas ou não
+    pub peso_total: f64,
+}
+
+/// Mapa compartilhado entre as threads de trabalho que executam as formigas.
+///
+/// Cada célula tem o seu próprio lock, de modo que formigas em regiões diferentes
+/// não disputam o mesmo mutex. Pegar e largar só acontecem com o lock da célula,
+/// então um grão nunca é pego por duas formigas.
+#[derive(Debug)]
+pub struct Mapa {
+    grade: Grade,
+    celulas: Vec<Mutex<Vec<Grao>>>,
+    feromonio: Option<CampoFeromonio>,
+    /// Modelo de pilhas: as células guardam vários grãos, as formigas pegam do topo e a
+    /// vizinhança é vista pelos centroides das pilhas
+    pilhas: bool,
+    /// Células ocupadas por formigas, quando só uma formiga pode ficar em cada célula
+    ocupacao: Option<Vec<AtomicBool>>,
+}
+
+impl Mapa {
+    /// Cria o mapa e distribui os grãos nas suas células
+    pub fn new(grade: Grade, graos: Vec<Grao>) -> Self {
+        let mut celulas: Vec<Vec<Grao>> = vec![vec![]; grade.num_celulas()];
+        for mut grao in graos {
+            // Grãos posicionados fora da grade são trazidos para dentro dela
+            grao.posicao = grade.ajustar(grao.posicao);
+            let indice = grade.indice(&grao.posicao).expect("Posição fora da grade");
+            celulas[indice].push(grao);
+        }
+
+        Mapa {
+            grade,
+            celulas: celulas.into_iter().map(Mutex::new).collect(),
+            feromonio: None,
+            pilhas: false,
+            ocupacao: None,
+        }
+    }
+
+    pub fn grade(&self) -> &Grade {
+        &self.grade
+    }
+
+    /// Cria a camada de feromônio, inicialmente vazia
+    pub fn ativar_feromonio(&mut self) {
+        self.feromonio = Some(CampoFeromonio::new(self.grade));
+    }
+
+    pub fn feromonio(&self) -> Option<&CampoFeromonio> {
+        self.feromonio.as_ref()
+    }
+
+    pub fn ativar_pilhas(&mut self) {
+        self.pilhas = true;
+    }
+
+    pub fn usa_pilhas(&self) -> bool {
+        self.pilhas
+    }
+
+    /// Passa a permitir no máximo uma formiga por célula
+    pub fn ativar_ocupacao(&mut self) {
+        self.ocupacao = Some(
+            (0..self.grade.num_celulas())
+                .map(|_| AtomicBool::new(false))
+                .collect(),
+        );
+    }
+
+    /// Marca a célula como ocupada por uma formiga. Retorna falso se ela já estava ocupada
+    /// ou está fora da grade. Sem a regra de ocupação sempre dá certo.
+    pub fn ocupar(&self, local: &Ponto) -> bool {
+        match (&self.ocupacao, self.grade.indice(local)) {
+            (None, _) => true,
+            (Some(ocupacao), Some(i)) => ocupacao[i]
+                .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok(),
+            (Some(_), None) => false,
+        }
+    }
+
+    fn desocupar(&self, local: &Ponto) {
+        if let (Some(ocupacao), Some(i)) = (&self.ocupacao, self.grade.indice(local)) {
+            ocupacao[i].store(false, Ordering::Release);
+        }
+    }
+
+    /// Leva uma formiga de `origem` para `destino` e retorna onde ela ficou: no destino, ou
+    /// na origem se outra formiga já ocupa o destino
+    pub fn mover_formiga(&self, origem: &Ponto, destino: Ponto) -> Ponto {
+        if self.ocupacao.is_none() || *origem == destino {
+            return destino;
+        }
+
+        if self.ocupar(&destino) {
+            self.desocupar(origem);
+            destino
+        } else {
+            *origem
+        }
+    }
+
+    /// Restrições desrespeitadas no estado atual: células com mais de um grão fora do
+    /// modelo de pilhas e, com a regra de ocupação, células com mais de uma formiga
+    pub fn violacoes(&self, formigas: &[Ponto]) -> Vec<String> {
+        let mut violacoes: Vec<String> = vec![];
+
+        if !self.pilhas {
+            for (indice, celula) in self.celulas.iter().enumerate() {
+                let quantidade = celula
+                    .lock()
+                    .expect("Não foi possivel dar lock em uma célula")
+                    .len();
+                if quantidade > 1 {
+                    let (x, y) = (
+                        indice as i32 % self.grade.largura,
+                        indice as i32 / self.grade.largura,
+                    );
+                    violacoes.push(format!("célula ({}, {}) com {} grãos", x, y, quantidade));
+                }
+            }
+        }
+
+        if self.ocupacao.is_some() {
+            let mut por_celula: HashMap<(i32, i32), usize> = HashMap::new();
+            for formiga in formigas {
+                *por_celula.entry((formiga.x, formiga.y)).or_insert(0) += 1;
+            }
+
+            let mut repetidas: Vec<_> = por_celula
+                .into_iter()
+                .filter(|(_, quantidade)| *quantidade > 1)
+                .collect();
+            repetidas.sort();
+            for ((x, y), quantidade) in repetidas {
+                violacoes.push(format!("célula ({}, {}) com {} formigas", x, y, quantidade));
+            }
+        }
+
+        violacoes
+    }
+
+    fn celula(&self, local: &Ponto) -> Option<std::sync::MutexGuard<'_, Vec<Grao>>> {
+        self.grade.indice(local).map(|i| {
+            self.celulas[i]
+                .lock()
+                .expect("Não foi possivel dar lock em uma célula")
+        })
+    }
+
+    /// Grão da célula que pode ser pego: o primeiro, ou o do topo no modelo de pilhas
+    pub fn grao_local(&self, local: &Ponto) -> Option<Grao> {
+        self.celula(local).and_then(|celula| {
+            if self.pilhas {
+                celula.last().cloned()
+            } else {
+                celula.first().cloned()
+            }
+        })
+    }
+
+    pub fn celula_livre(&self, local: &Ponto) -> bool {
+        self.celula(local).is_some_and(|celula| celula.is_empty())
+    }
+
+    /// Se uma formiga pode largar um grão na célula: só nas livres, ou em qualquer uma
+    /// no modelo de pilhas
+    pub fn aceita_grao(&self, local: &Ponto) -> bool {
+        if self.pilhas {
+            self.grade.contem(local)
+        } else {
+            self.celula_livre(local)
+        }
+    }
+
+    /// Cópia dos grãos na vizinhança de `local`, cada um com o peso da sua célula. No
+    /// modelo de pilhas cada pilha aparece como um só grão, o seu centroide, e o grão
+    /// `avaliado` fica fora do centroide da sua pilha, esteja ele sozinho ou não.
+    pub fn graos_redor(
+        &self,
+        local: &Ponto,
+        raio: i32,
+        formato: FormatoVizinhanca,
+        sigma: f64,
+        avaliado: Option<uuid::Uuid>,
+    ) -> Vizinhanca {
+        let mut resultado = Vizinhanca {
+            graos: vec![],
+            peso_total: 0.0,
+        };
+
+        for (vizinho, peso) in self.grade.vizinhanca_ponderada(local, raio, formato, sigma) {
+            if let Some(celula) = self.celula(&vizinho) {
+                if !self.pilhas {
+                    resultado
+                        .graos
+                        .extend(celula.iter().map(|g| (g.clone(), peso)));
+                } else if let Some(centroide) = centroide(&celula, avaliado) {
+                    resultado.graos.push((centroide, peso));
+                }
+                resultado.peso_total += peso;
+            }
+        }
+
+        resultado
+    }
+
+    /// Retira o grão `id` da célula. Retorna `None` se outra formiga já o pegou.
+    pub fn pegar(&self, local: &Ponto, id: uuid::Uuid) -> Option<Grao> {
+        let mut celula = self.celula(local)?;
+        let posicao = celula.iter().position(|g| g.id == id)?;
+
+        Some(celula.remove(posicao))
+    }
+
+    /// Larga o grão se a célula ainda estiver livre (ou no topo da pilha, no modelo de
+    /// pilhas); caso contrário devolve o grão
+    pub fn largar(&self, local: &Ponto, mut grao: Grao) -> Result<(), Grao> {
+        match self.celula(local) {
+            Some(mut celula) if self.pilhas || celula.is_empty() => {
+                grao.posicao = *local;
+                celula.push(grao);
+                Ok(())
+            }
+            _ => Err(grao),
+        }
+    }
+
+    /// Troca o grão `id` da célula por `grao`. Retorna o grão retirado, ou devolve `grao` se
+    /// outra formiga já pegou o grão `id`.
+    pub fn trocar(&self, local: &Ponto, id: uuid::Uuid, mut grao: Grao) -> Result<Grao, Grao> {
+        let mut celula = match self.celula(local) {
+            Some(celula) => celula,
+            None => return Err(grao),
+        };
+
+        match celula.iter().position(|g| g.id == id) {
+            Some(indice) => {
+                grao.posicao = *local;
+                Ok(std::mem::replace(&mut celula[indice], grao))
+            }
+            None => Err(grao),
+        }
+    }
+
+    /// Coloca o grão mesmo que a célula esteja ocupada
+    pub fn empilhar(&self, local: &Ponto, mut grao: Grao) {
+        if let Some(mut celula) = self.celula(local) {
+            grao.posicao = *local;
+            celula.push(grao);
+        }
+    }
+
+    /// Procura a célula livre mais próxima de `local`, percorrendo anéis cada vez maiores
+    pub fn celula_livre_proxima(&self, local: &Ponto) -> Option<Ponto> {
+        for raio in 0..=self.grade.largura.max(self.grade.altura) {
+            for (celula, distancia) in self.grade.vizinhanca(local, raio) {
+                if distancia == raio && self.celula_livre(&celula) {
+                    return Some(celula);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Cópia dos grãos de cada célula ocupada
+    pub fn pilhas(&self) -> Vec<Vec<Grao>> {
+        self.celulas
+            .iter()
+            .map(|celula| {
+                celula
+                    .lock()
+                    .expect("Não foi possivel dar lock em uma célula")
+                    .clone()
+            })
+            .filter(|celula| !celula.is_empty())
+            .collect()
+    }
+
+    /// Cópia de todos os grãos do mapa
+    pub fn graos(&self) -> Vec<Grao> {
+        self.celulas
+            .iter()
+            .flat_map(|celula| {
+                celula
+                    .lock()
+                    .expect("Não foi possivel dar lock em uma célula")
+                    .clone()
+            })
+            .collect()
+    }
+}
+
+/// Grão fictício com a média dos dados da pilha sem o grão `sem`, na posição e no grupo do
+/// grão do topo que sobra. O id nulo garante que ele nunca é confundido com um grão de
+/// verdade; uma pilha que fica com um grão só é representada por ele mesmo.
+fn centroide(pilha: &[Grao], sem: Option<uuid::Uuid>) -> Option<Grao> {
+    let pilha: Vec<&Grao> = pilha.iter().filter(|grao| Some(grao.id) != sem).collect();
+    let topo = *pilha.last()?;
+    if pilha.len() == 1 {
+        return Some(topo.clone());
+    }
+
+    let mut dados = vec![0.0; topo.dados.len()];
+    for grao in &pilha {
+        for (soma, valor) in dados.iter_mut().zip(&grao.dados) {
+            *soma += valor / pilha.len() as f64;
+        }
+    }
+
+    Some(Grao {
+        id: uuid::Uuid::nil(),
+        posicao: topo.posicao,
+        dados,
+        grupo: topo.grupo,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::grade::{Fronteira, Topologia};
+
+    fn grao(valor: f64) -> Grao {
+        Grao::new(Ponto { x: 1, y: 1 }, vec![valor], 0)
+    }
+
+    fn mapa_de_pilhas(graos: Vec<Grao>) -> Mapa {
+        let grade = Grade::new(5, 5, Fronteira::Toroidal, Topologia::Quadrada);
+        let mut mapa = Mapa::new(grade, graos);
+        mapa.ativar_pilhas();
+        mapa
+    }
+
+    fn vizinhos(mapa: &Mapa, avaliado: &Grao) -> Vec<Vec<f64>> {
+        mapa.graos_redor(
+            &avaliado.posicao,
+            1,
+            FormatoVizinhanca::Moore,
+            1.0,
+            Some(avaliado.id),
+        )
+        .graos
+        .into_iter()
+        .map(|(grao, _)| grao.dados)
+        .collect()
+    }
+
+    #[test]
+    fn pilha_do_grao_avaliado_fica_sem_ele() {
+        // Sozinho, o grão não tem vizinhos
+        let sozinho = grao(0.0);
+        let mapa = mapa_de_pilhas(vec![sozinho.clone()]);
+        assert!(vizinhos(&mapa, &sozinho).is_empty());
+
+        // Numa pilha, a célula entra pelo centroide dos outros grãos dela
+        let (a, b, c) = (grao(0.0), grao(0.2), grao(0.4));
+        let mapa = mapa_de_pilhas(vec![a.clone(), b.clone()]);
+        assert_eq!(vizinhos(&mapa, &a), vec![vec![0.2]]);
+
+        let mapa = mapa_de_pilhas(vec![a.clone(), b, c]);
+        let dados = vizinhos(&mapa, &a);
+        assert_eq!(dados.len(), 1);
+        assert!((dados[0][0] - 0.3).abs() < 1e-12);
+
+        // Sem grão avaliado a pilha inteira conta
+        let vizinhanca = mapa.graos_redor(&a.posicao, 1, FormatoVizinhanca::Moore, 1.0, None);
+        assert!((vizinhanca.graos[0].0.dados[0] - 0.2).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,25 @@
+pub mod agenda;
+pub mod anomalias;
+pub mod anttree;
+pub mod automatico;
+pub mod avaliacao;
+pub mod cenario;
+pub mod classicos;
+pub mod configuracao;
+pub mod constantes;
+pub mod densidade;
+pub mod enxame;
+pub mod estagnacao;
+pub mod feromonio;
+pub mod formiga;
+pub mod grade;
+pub mod grao;
+pub mod mapa;
+pub mod modelos;
+pub mod movimento;
+pub mod outros;
+pub mod particulas;
+pub mod populacao;
+pub mod refinamento;
+pub mod restricoes;
+pub mod trabalhadores;
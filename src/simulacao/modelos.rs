@@ -0,0 +1,203 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Constantes de limiar dos modelos de probabilidade
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Limiares {
+    pub k1: f64,
+    pub k2: f64,
+}
+
+/// Modelo das probabilidades de pegar (`pp`) e largar (`pd`) um grão a partir da sua
+/// densidade local `f`
+pub trait ModeloProbabilidade: Debug + Send + Sync {
+    /// Nome usado para escolher o modelo nos arquivos de cenário
+    fn nome(&self) -> &'static str;
+    fn pp(&self, f: f64, limiares: &Limiares) -> f64;
+    fn pd(&self, f: f64, limiares: &Limiares) -> f64;
+}
+
+/// Modelo de Deneubourg: `pp = (k1 / (k1 + f))²` e `pd = (f / (k2 + f))²`
+#[derive(Clone, Copy, Debug)]
+pub struct Quadratico;
+
+impl ModeloProbabilidade for Quadratico {
+    fn nome(&self) -> &'static str {
+        "quadratico"
+    }
+
+    fn pp(&self, f: f64, limiares: &Limiares) -> f64 {
+        (limiares.k1 / (limiares.k1 + f)).powi(2)
+    }
+
+    fn pd(&self, f: f64, limiares: &Limiares) -> f64 {
+        (f / (limiares.k2 + f)).powi(2)
+    }
+}
+
+/// Variante de Lumer e Faieta: pega como Deneubourg, mas larga com `pd = 2f` abaixo do
+/// limiar `k2` e sempre a partir dele
+#[derive(Clone, Copy, Debug)]
+pub struct LumerFaieta;
+
+impl ModeloProbabilidade for LumerFaieta {
+    fn nome(&self) -> &'static str {
+        "lumer_faieta"
+    }
+
+    fn pp(&self, f: f64, limiares: &Limiares) -> f64 {
+        (limiares.k1 / (limiares.k1 + f)).powi(2)
+    }
+
+    fn pd(&self, f: f64, limiares: &Limiares) -> f64 {
+        if f < limiares.k2 {
+            2.0 * f
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Modelo sigmoide: com `s(f) = (1 - e^(-c·f)) / (1 + e^(-c·f))`, `pp = 1 - s(f)` e
+/// `pd = s(f)`. A inclinação `c` controla a rapidez da transição.
+#[derive(Clone, Copy, Debug)]
+pub struct Sigmoide {
+    pub inclinacao: f64,
+}
+
+impl Sigmoide {
+    fn s(&self, f: f64) -> f64 {
+        let e = (-self.inclinacao * f).exp();
+        (1.0 - e) / (1.0 + e)
+    }
+}
+
+impl ModeloProbabilidade for Sigmoide {
+    fn nome(&self) -> &'static str {
+        "sigmoide"
+    }
+
+    fn pp(&self, f: f64, _limiares: &Limiares) -> f64 {
+        1.0 - self.s(f)
+    }
+
+    fn pd(&self, f: f64, _limiares: &Limiares) -> f64 {
+        self.s(f)
+    }
+}
+
+/// Modelo do ATTA de Handl et al.: pega sempre com `f ≤ 1` e com `1/f²` acima; larga
+/// sempre com `f ≥ 1` e com `f⁴` abaixo
+#[derive(Clone, Copy, Debug)]
+pub struct Atta;
+
+impl ModeloProbabilidade for Atta {
+    fn nome(&self) -> &'static str {
+        "atta"
+    }
+
+    fn pp(&self, f: f64, _limiares: &Limiares) -> f64 {
+        if f <= 1.0 {
+            1.0
+        } else {
+            1.0 / f.powi(2)
+        }
+    }
+
+    fn pd(&self, f: f64, _limiares: &Limiares) -> f64 {
+        if f >= 1.0 {
+            1.0
+        } else {
+            f.powi(4)
+        }
+    }
+}
+
+/// Cria um modelo pelo nome. `inclinacao` só é usada pelo modelo sigmoide.
+pub fn modelo_por_nome(
+    nome: &str,
+    inclinacao: f64,
+) -> Result<Arc<dyn ModeloProbabilidade>, String> {
+    match nome {
+        "quadratico" => Ok(Arc::new(Quadratico)),
+        "lumer_faieta" => Ok(Arc::new(LumerFaieta)),
+        "sigmoide" => Ok(Arc::new(Sigmoide { inclinacao })),
+        "atta" => Ok(Arc::new(Atta)),
+        _ => Err(format!("modelo de probabilidade desconhecido `{}`", nome)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMIARES: Limiares = Limiares { k1: 0.1, k2: 0.15 };
+
+    fn perto(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    #[test]
+    fn quadratico() {
+        let modelo = Quadratico;
+        assert_eq!(modelo.pp(0.0, &LIMIARES), 1.0);
+        assert_eq!(modelo.pd(0.0, &LIMIARES), 0.0);
+        // Em f = k1 pega com 1/4, e em f = k2 larga com 1/4
+        assert!(perto(modelo.pp(0.1, &LIMIARES), 0.25));
+        assert!(perto(modelo.pd(0.15, &LIMIARES), 0.25));
+    }
+
+    #[test]
+    fn lumer_faieta_larga_sempre_a_partir_de_k2() {
+        let modelo = LumerFaieta;
+        assert!(perto(modelo.pp(0.1, &LIMIARES), 0.25));
+        assert_eq!(modelo.pd(0.0, &LIMIARES), 0.0);
+        assert!(perto(modelo.pd(0.05, &LIMIARES), 0.1));
+        assert!(perto(modelo.pd(0.149, &LIMIARES), 0.298));
+        assert_eq!(modelo.pd(0.15, &LIMIARES), 1.0);
+        assert_eq!(modelo.pd(3.0, &LIMIARES), 1.0);
+    }
+
+    #[test]
+    fn atta_muda_de_regime_em_f_igual_a_1() {
+        let modelo = Atta;
+        for f in [0.0, 0.5, 1.0] {
+            assert_eq!(modelo.pp(f, &LIMIARES), 1.0);
+        }
+        assert!(perto(modelo.pp(2.0, &LIMIARES), 0.25));
+
+        assert_eq!(modelo.pd(0.0, &LIMIARES), 0.0);
+        assert!(perto(modelo.pd(0.5, &LIMIARES), 0.0625));
+        for f in [1.0, 2.0] {
+            assert_eq!(modelo.pd(f, &LIMIARES), 1.0);
+        }
+    }
+
+    #[test]
+    fn sigmoide_e_simetrica() {
+        let modelo = Sigmoide { inclinacao: 2.0 };
+        assert_eq!(modelo.pp(0.0, &LIMIARES), 1.0);
+        assert_eq!(modelo.pd(0.0, &LIMIARES), 0.0);
+
+        // s(f) = tanh(c·f / 2)
+        let f: f64 = 0.7;
+        assert!(perto(modelo.pd(f, &LIMIARES), f.tanh()));
+        assert!(perto(
+            modelo.pp(f, &LIMIARES) + modelo.pd(f, &LIMIARES),
+            1.0
+        ));
+        assert!(modelo.pd(20.0, &LIMIARES) > 0.999_999);
+
+        // Quanto maior a inclinação, mais rápida a transição
+        let ingreme = Sigmoide { inclinacao: 10.0 };
+        assert!(ingreme.pd(f, &LIMIARES) > modelo.pd(f, &LIMIARES));
+    }
+
+    #[test]
+    fn modelos_pelo_nome() {
+        for nome in ["quadratico", "lumer_faieta", "sigmoide", "atta"] {
+            assert_eq!(modelo_por_nome(nome, 1.0).unwrap().nome(), nome);
+        }
+        assert!(modelo_por_nome("outro", 1.0).is_err());
+    }
+}
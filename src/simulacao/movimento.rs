@@ -0,0 +1,268 @@
+use super::configuracao::{Configuracao, ParametrosPasso};
+use super::densidade::similaridade;
+use super::formiga::Formiga;
+use super::mapa::Mapa;
+use rand::distributions::WeightedIndex;
+use rand::prelude::Distribution;
+use rand::Rng;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Peso mínimo de cada direção na caminhada tendenciosa, para que a formiga ainda possa
+/// ir para células sem vizinhos parecidos
+const PESO_MINIMO_DIRECAO: f64 = 0.05;
+
+/// Para onde uma formiga anda: a direção tomada e quantas células ela percorre
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Movimento {
+    pub direcao: usize,
+    pub passos: i32,
+}
+
+/// Estratégia de movimento das formigas que não têm um destino lembrado
+pub trait EstrategiaMovimento: Debug + Send + Sync {
+    /// Nome usado para escolher a estratégia nos arquivos de cenário
+    fn nome(&self) -> &'static str;
+    fn mover(
+        &self,
+        formiga: &Formiga,
+        mapa: &Mapa,
+        configuracao: &Configuracao,
+        parametros: &ParametrosPasso,
+    ) -> Movimento;
+}
+
+/// Caminhada aleatória uniforme: uma célula numa direção sorteada
+#[derive(Clone, Copy, Debug)]
+pub struct Uniforme;
+
+impl EstrategiaMovimento for Uniforme {
+    fn nome(&self) -> &'static str {
+        "uniforme"
+    }
+
+    fn mover(
+        &self,
+        formiga: &Formiga,
+        mapa: &Mapa,
+        configuracao: &Configuracao,
+        _parametros: &ParametrosPasso,
+    ) -> Movimento {
+        andar(direcao_aleatoria(formiga, mapa, configuracao), 1)
+    }
+}
+
+/// Caminhada correlacionada: repete a última direção com probabilidade `persistencia`
+#[derive(Clone, Copy, Debug)]
+pub struct Persistente {
+    pub persistencia: f64,
+}
+
+impl EstrategiaMovimento for Persistente {
+    fn nome(&self) -> &'static str {
+        "persistente"
+    }
+
+    fn mover(
+        &self,
+        formiga: &Formiga,
+        mapa: &Mapa,
+        configuracao: &Configuracao,
+        _parametros: &ParametrosPasso,
+    ) -> Movimento {
+        let direcao = match formiga.ultima_direcao {
+            Some(direcao) if rand::thread_rng().gen_bool(self.persistencia.clamp(0.0, 1.0)) => {
+                direcao
+            }
+            _ => direcao_aleatoria(formiga, mapa, configuracao),
+        };
+
+        andar(direcao, 1)
+    }
+}
+
+/// Voo de Lévy: direção sorteada e comprimento com cauda pesada, `P(l) ~ l^-expoente`,
+/// limitado a metade do maior lado da grade
+#[derive(Clone, Copy, Debug)]
+pub struct Levy {
+    pub expoente: f64,
+}
+
+impl EstrategiaMovimento for Levy {
+    fn nome(&self) -> &'static str {
+        "levy"
+    }
+
+    fn mover(
+        &self,
+        formiga: &Formiga,
+        mapa: &Mapa,
+        configuracao: &Configuracao,
+        _parametros: &ParametrosPasso,
+    ) -> Movimento {
+        let grade = mapa.grade();
+        let maximo = (grade.largura.max(grade.altura) / 2).max(1);
+
+        // Amostragem pela inversa da distribuição de Pareto com mínimo 1
+        let u: f64 = rand::thread_rng().gen_range(f64::EPSILON..=1.0);
+        let comprimento = u.powf(-1.0 / (self.expoente - 1.0).max(f64::EPSILON));
+        let passos = (comprimento.floor() as i64).clamp(1, maximo as i64) as i32;
+
+        andar(direcao_aleatoria(formiga, mapa, configuracao), passos)
+    }
+}
+
+/// Saltos de `velocidade` células numa direção sorteada
+#[derive(Clone, Copy, Debug)]
+pub struct Saltos {
+    pub velocidade: i32,
+}
+
+impl EstrategiaMovimento for Saltos {
+    fn nome(&self) -> &'static str {
+        "saltos"
+    }
+
+    fn mover(
+        &self,
+        formiga: &Formiga,
+        mapa: &Mapa,
+        configuracao: &Configuracao,
+        _parametros: &ParametrosPasso,
+    ) -> Movimento {
+        andar(
+            direcao_aleatoria(formiga, mapa, configuracao),
+            self.velocidade.max(1),
+        )
+    }
+}
+
+/// Caminhada tendenciosa: a formiga carregada sorteia a direção com peso proporcional à
+/// similaridade do grão que carrega com a vizinhança da célula vizinha; sem carga anda
+/// como na caminhada uniforme
+#[derive(Clone, Copy, Debug)]
+pub struct Tendenciosa;
+
+impl EstrategiaMovimento for Tendenciosa {
+    fn nome(&self) -> &'static str {
+        "tendenciosa"
+    }
+
+    fn mover(
+        &self,
+        formiga: &Formiga,
+        mapa: &Mapa,
+        configuracao: &Configuracao,
+        parametros: &ParametrosPasso,
+    ) -> Movimento {
+        let grao = match &formiga.segurando_objeto {
+            Some(grao) => grao,
+            None => return andar(direcao_aleatoria(formiga, mapa, configuracao), 1),
+        };
+
+        let grade = mapa.grade();
+        let pesos: Vec<f64> = (0..grade.num_direcoes())
+            .map(|direcao| {
+                let vizinho = grade.mover(&formiga.posicao, direcao, 1);
+                let vizinhanca = mapa.graos_redor(
+                    &vizinho,
+                    parametros.raio_vizinhanca,
+                    configuracao.formato_vizinhanca,
+                    configuracao.sigma_vizinhanca,
+                    Some(grao.id),
+                );
+                similaridade(grao, &vizinhanca, configuracao, parametros) + PESO_MINIMO_DIRECAO
+            })
+            .collect();
+
+        let direcao = match WeightedIndex::new(&pesos) {
+            Ok(distribuicao) => distribuicao.sample(&mut rand::thread_rng()),
+            Err(_) => direcao_aleatoria(formiga, mapa, configuracao),
+        };
+
+        andar(direcao, 1)
+    }
+}
+
+/// Direção sorteada pelas estratégias. Sem feromônio, ou com a formiga carregada, todas
+/// as direções têm a mesma chance; com feromônio, a formiga sem carga sorteia cada direção
+/// com peso `1 + atracao · τ`, em que `τ` é o feromônio da célula vizinha naquela direção.
+fn direcao_aleatoria(formiga: &Formiga, mapa: &Mapa, configuracao: &Configuracao) -> usize {
+    let grade = mapa.grade();
+    let mut rng = rand::thread_rng();
+
+    let campo = match mapa.feromonio() {
+        Some(campo) if formiga.segurando_objeto.is_none() => campo,
+        // Uma das direções da grade (8 na quadrada, 6 na hexagonal)
+        _ => return rng.gen_range(0..grade.num_direcoes()),
+    };
+
+    let pesos: Vec<f64> = (0..grade.num_direcoes())
+        .map(|direcao| {
+            1.0 + configuracao.atracao_feromonio
+                * campo.valor(&grade.mover(&formiga.posicao, direcao, 1))
+        })
+        .collect();
+
+    match WeightedIndex::new(&pesos) {
+        Ok(distribuicao) => distribuicao.sample(&mut rng),
+        Err(_) => rng.gen_range(0..grade.num_direcoes()),
+    }
+}
+
+fn andar(direcao: usize, passos: i32) -> Movimento {
+    Movimento { direcao, passos }
+}
+
+/// Cria uma estratégia de movimento pelo nome. `persistencia`, `expoente_levy` e
+/// `velocidade` só são usados pelas estratégias correspondentes.
+pub fn movimento_por_nome(
+    nome: &str,
+    persistencia: f64,
+    expoente_levy: f64,
+    velocidade: i32,
+) -> Result<Arc<dyn EstrategiaMovimento>, String> {
+    match nome {
+        "uniforme" => Ok(Arc::new(Uniforme)),
+        "persistente" => Ok(Arc::new(Persistente { persistencia })),
+        "levy" => Ok(Arc::new(Levy {
+            expoente: expoente_levy,
+        })),
+        "saltos" => Ok(Arc::new(Saltos { velocidade })),
+        "tendenciosa" => Ok(Arc::new(Tendenciosa)),
+        _ => Err(format!("estratégia de movimento desconhecida `{}`", nome)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::formiga::Perfil;
+    use crate::simulacao::outros::Ponto;
+
+    #[test]
+    fn feromonio_inclina_a_direcao_sem_trocar_a_estrategia() {
+        let configuracao = Configuracao {
+            atracao_feromonio: 1e9,
+            ..Configuracao::default()
+        };
+        let mut mapa = Mapa::new(configuracao.grade, vec![]);
+        mapa.ativar_feromonio();
+
+        let formiga = Formiga::new(Ponto { x: 10, y: 10 }, 1.0, Perfil::default());
+        let alvo = Ponto { x: 11, y: 10 };
+        mapa.feromonio().unwrap().depositar(&alvo, 1.0);
+        let grade = mapa.grade();
+        let direcao = (0..grade.num_direcoes())
+            .find(|direcao| grade.mover(&formiga.posicao, *direcao, 1) == alvo)
+            .unwrap();
+
+        let perfil = Perfil::default();
+        let parametros = configuracao.parametros_no_passo(0, 1.0, &perfil);
+        let saltos = Saltos { velocidade: 3 };
+        for _ in 0..20 {
+            let movimento = saltos.mover(&formiga, &mapa, &configuracao, &parametros);
+            assert_eq!(movimento, Movimento { direcao, passos: 3 });
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use super::configuracao::Configuracao;
+use super::densidade::distancia_entre_par_de_dados;
+use super::enxame::{grupos_padrao, AlgoritmoEnxame};
+use super::grao::Grao;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Agrupamento por enxame de partículas (van der Merwe e Engelbrecht): cada partícula é
+/// um conjunto de `k` centroides e o enxame minimiza o erro de quantização, a média da
+/// distância dos grãos ao centroide mais próximo em cada agrupamento.
+#[derive(Clone, Debug)]
+pub struct EnxameParticulas {
+    graos: Vec<Grao>,
+    grupos: usize,
+    particulas: Vec<Particula>,
+    /// Melhor posição encontrada pelo enxame e o seu erro
+    melhor: Vec<f64>,
+    erro_melhor: f64,
+    iteracao: usize,
+    num_particulas: usize,
+    iteracoes: usize,
+    inercia: f64,
+    cognitivo: f64,
+    social: f64,
+}
+
+#[derive(Clone, Debug)]
+struct Particula {
+    /// Centroides concatenados, `grupos` vetores com a dimensão dos dados
+    posicao: Vec<f64>,
+    velocidade: Vec<f64>,
+    melhor: Vec<f64>,
+    erro_melhor: f64,
+}
+
+impl EnxameParticulas {
+    pub fn new(graos: Vec<Grao>, configuracao: &Configuracao) -> EnxameParticulas {
+        let grupos = configuracao
+            .grupos
+            .unwrap_or_else(|| grupos_padrao(graos.len()))
+            .clamp(1, graos.len().max(1));
+
+        EnxameParticulas {
+            graos,
+            grupos,
+            particulas: vec![],
+            melhor: vec![],
+            erro_melhor: f64::INFINITY,
+            iteracao: 0,
+            num_particulas: configuracao.num_particulas,
+            iteracoes: configuracao.iteracoes_particulas,
+            inercia: configuracao.inercia_particulas,
+            cognitivo: configuracao.cognitivo_particulas,
+            social: configuracao.social_particulas,
+        }
+    }
+
+    fn dimensao(&self) -> usize {
+        self.graos.first().map_or(0, |grao| grao.dados.len())
+    }
+
+    /// Índice do centroide mais próximo de cada grão
+    fn rotular(&self, posicao: &[f64]) -> Vec<usize> {
+        let dimensao = self.dimensao().max(1);
+        self.graos
+            .iter()
+            .map(|grao| {
+                posicao
+                    .chunks(dimensao)
+                    .map(|centroide| distancia_entre_par_de_dados(&grao.dados, centroide))
+                    .enumerate()
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map_or(0, |(indice, _)| indice)
+            })
+            .collect()
+    }
+
+    /// Erro de quantização: média, entre os agrupamentos não vazios, da distância média
+    /// dos seus grãos ao centroide
+    fn erro(&self, posicao: &[f64]) -> f64 {
+        let dimensao = self.dimensao().max(1);
+        let mut soma = vec![0.0; self.grupos];
+        let mut quantidade = vec![0usize; self.grupos];
+        for (grao, rotulo) in self.graos.iter().zip(self.rotular(posicao)) {
+            let centroide = &posicao[rotulo * dimensao..(rotulo + 1) * dimensao];
+            soma[rotulo] += distancia_entre_par_de_dados(&grao.dados, centroide);
+            quantidade[rotulo] += 1;
+        }
+
+        let medias: Vec<f64> = soma
+            .iter()
+            .zip(&quantidade)
+            .filter(|(_, &quantidade)| quantidade > 0)
+            .map(|(soma, &quantidade)| soma / quantidade as f64)
+            .collect();
+        medias.iter().sum::<f64>() / medias.len().max(1) as f64
+    }
+}
+
+impl AlgoritmoEnxame for EnxameParticulas {
+    fn nome(&self) -> &'static str {
+        "enxame de partículas"
+    }
+
+    /// Cada partícula começa com centroides em grãos sorteados e velocidade nula
+    fn inicializar(&mut self) {
+        let mut rng = rand::thread_rng();
+        self.particulas = (0..self.num_particulas)
+            .map(|_| {
+                let posicao: Vec<f64> = self
+                    .graos
+                    .choose_multiple(&mut rng, self.grupos)
+                    .flat_map(|grao| grao.dados.clone())
+                    .collect();
+                let erro = self.erro(&posicao);
+                Particula {
+                    velocidade: vec![0.0; posicao.len()],
+                    melhor: posicao.clone(),
+                    posicao,
+                    erro_melhor: erro,
+                }
+            })
+            .collect();
+
+        if let Some(melhor) = self
+            .particulas
+            .iter()
+            .min_by(|a, b| a.erro_melhor.total_cmp(&b.erro_melhor))
+        {
+            self.melhor = melhor.melhor.clone();
+            self.erro_melhor = melhor.erro_melhor;
+        }
+        self.iteracao = 0;
+    }
+
+    /// Uma iteração: move todas as partículas e atualiza as melhores posições
+    fn passo(&mut self) {
+        let mut rng = rand::thread_rng();
+        let mut particulas = std::mem::take(&mut self.particulas);
+        for particula in &mut particulas {
+            for i in 0..particula.posicao.len() {
+                let cognitivo = self.cognitivo
+                    * rng.gen::<f64>()
+                    * (particula.melhor[i] - particula.posicao[i]);
+                let social =
+                    self.social * rng.gen::<f64>() * (self.melhor[i] - particula.posicao[i]);
+                particula.velocidade[i] =
+                    self.inercia * particula.velocidade[i] + cognitivo + social;
+                particula.posicao[i] += particula.velocidade[i];
+            }
+
+            let erro = self.erro(&particula.posicao);
+            if erro < particula.erro_melhor {
+                particula.erro_melhor = erro;
+                particula.melhor = particula.posicao.clone();
+            }
+            if erro < self.erro_melhor {
+                self.erro_melhor = erro;
+                self.melhor = particula.posicao.clone();
+            }
+        }
+        self.particulas = particulas;
+        self.iteracao += 1;
+
+        if self.iteracao.is_multiple_of(50) || self.terminou() {
+            println!(
+                "Iteração {}: erro de quantização {:.4}",
+                self.iteracao, self.erro_melhor
+            );
+        }
+    }
+
+    fn terminou(&self) -> bool {
+        self.iteracao >= self.iteracoes || self.particulas.is_empty()
+    }
+
+    fn agrupamentos(&self) -> (Vec<Grao>, Vec<usize>) {
+        (self.graos.clone(), self.rotular(&self.melhor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::enxame::executar;
+    use crate::simulacao::outros::Ponto;
+
+    /// Duas nuvens de grãos, em torno de (0, 0) e de (100, 100)
+    fn duas_nuvens() -> Vec<Grao> {
+        (0..40)
+            .map(|i| {
+                let grupo = i / 20;
+                let base = 100.0 * grupo as f64;
+                let desvio = (i % 5) as f64 * 0.2;
+                Grao::new(
+                    Ponto { x: 0, y: 0 },
+                    vec![base + desvio, base - desvio],
+                    grupo,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nuvens_separadas_ganham_um_rotulo_cada() {
+        let configuracao = Configuracao {
+            grupos: Some(2),
+            ..Configuracao::default()
+        };
+        let mut enxame = EnxameParticulas::new(duas_nuvens(), &configuracao);
+
+        let avaliacao = executar(&mut enxame);
+        assert_eq!(avaliacao.agrupamentos, 2);
+        assert_eq!(avaliacao.pureza, 1.0);
+
+        let (graos, rotulos) = enxame.agrupamentos();
+        for (grao, rotulo) in graos.iter().zip(&rotulos) {
+            let primeiro_do_grupo = graos.iter().position(|g| g.grupo == grao.grupo).unwrap();
+            assert_eq!(*rotulo, rotulos[primeiro_do_grupo]);
+        }
+    }
+}
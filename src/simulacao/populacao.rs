@@ -0,0 +1,221 @@
+use super::modelos::modelo_por_nome;
+use std::fmt;
+use std::str::FromStr;
+
+/// Grupo de formigas com parâmetros próprios. Os parâmetros ausentes seguem a
+/// configuração geral.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Populacao {
+    /// Fração relativa das formigas que pertencem ao grupo
+    pub peso: f64,
+    /// Intervalo de onde o alpha inicial de cada formiga é sorteado (um valor fixo quando
+    /// os dois extremos são iguais)
+    pub alpha: Option<(f64, f64)>,
+    /// Quantas vezes o movimento de cada passo é aplicado
+    pub velocidade: Option<i32>,
+    pub raio_vizinhanca: Option<i32>,
+    /// Nome do modelo de probabilidade
+    pub modelo: Option<String>,
+}
+
+/// Lê populações no formato `<peso> [alpha:<valor>|<min>..<max>] [velocidade:<n>]
+/// [raio:<n>] [modelo:<nome>]`
+impl FromStr for Populacao {
+    type Err = String;
+
+    fn from_str(valor: &str) -> Result<Self, Self::Err> {
+        let mut partes = valor.split_whitespace();
+        let peso = partes
+            .next()
+            .and_then(|peso| peso.parse::<f64>().ok())
+            .filter(|peso| *peso > 0.0)
+            .ok_or_else(|| format!("população sem peso positivo `{}`", valor))?;
+
+        let mut populacao = Populacao {
+            peso,
+            alpha: None,
+            velocidade: None,
+            raio_vizinhanca: None,
+            modelo: None,
+        };
+
+        for parte in partes {
+            let (chave, valor) = parte
+                .split_once(':')
+                .ok_or_else(|| format!("esperado `chave:valor`, encontrado `{}`", parte))?;
+            let invalido = || format!("valor inválido em `{}`", parte);
+
+            match chave {
+                "alpha" => {
+                    let (minimo, maximo) = valor.split_once("..").unwrap_or((valor, valor));
+                    let minimo: f64 = minimo.parse().map_err(|_| invalido())?;
+                    let maximo: f64 = maximo.parse().map_err(|_| invalido())?;
+                    if minimo <= 0.0 || minimo > maximo {
+                        return Err(invalido());
+                    }
+                    populacao.alpha = Some((minimo, maximo));
+                }
+                "velocidade" => {
+                    let velocidade: i32 = valor.parse().map_err(|_| invalido())?;
+                    if velocidade < 1 {
+                        return Err(invalido());
+                    }
+                    populacao.velocidade = Some(velocidade);
+                }
+                "raio" => {
+                    let raio: i32 = valor.parse().map_err(|_| invalido())?;
+                    if raio < 0 {
+                        return Err(invalido());
+                    }
+                    populacao.raio_vizinhanca = Some(raio);
+                }
+                "modelo" => {
+                    modelo_por_nome(valor, 0.0)?;
+                    populacao.modelo = Some(valor.to_string());
+                }
+                _ => return Err(format!("parâmetro de população desconhecido `{}`", chave)),
+            }
+        }
+
+        Ok(populacao)
+    }
+}
+
+impl fmt::Display for Populacao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.peso)?;
+        match self.alpha {
+            Some((minimo, maximo)) if minimo == maximo => write!(f, " alpha:{}", minimo)?,
+            Some((minimo, maximo)) => write!(f, " alpha:{}..{}", minimo, maximo)?,
+            None => {}
+        }
+        if let Some(velocidade) = self.velocidade {
+            write!(f, " velocidade:{}", velocidade)?;
+        }
+        if let Some(raio) = self.raio_vizinhanca {
+            write!(f, " raio:{}", raio)?;
+        }
+        if let Some(modelo) = &self.modelo {
+            write!(f, " modelo:{}", modelo)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Divide `total` formigas entre as populações proporcionalmente aos pesos
+pub fn dividir_formigas(populacoes: &[Populacao], total: i32) -> Vec<i32> {
+    let soma: f64 = populacoes.iter().map(|p| p.peso).sum();
+    let mut restante = total;
+
+    populacoes
+        .iter()
+        .enumerate()
+        .map(|(i, populacao)| {
+            // A última população fica com o que sobrou dos arredondamentos
+            let quantidade = if i + 1 == populacoes.len() {
+                restante
+            } else {
+                ((populacao.peso / soma * total as f64).round() as i32).min(restante)
+            };
+            restante -= quantidade;
+            quantidade
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::configuracao::Configuracao;
+
+    fn populacao(peso: f64) -> Populacao {
+        Populacao {
+            peso,
+            alpha: None,
+            velocidade: None,
+            raio_vizinhanca: None,
+            modelo: None,
+        }
+    }
+
+    #[test]
+    fn divisao_soma_o_total() {
+        let populacoes: Vec<Populacao> = [1.0, 1.0, 1.0].map(populacao).to_vec();
+        assert_eq!(dividir_formigas(&populacoes, 10), vec![3, 3, 4]);
+
+        let populacoes: Vec<Populacao> = [2.0, 1.0, 0.5, 0.25].map(populacao).to_vec();
+        for total in 0..50 {
+            let divisao = dividir_formigas(&populacoes, total);
+            assert_eq!(divisao.iter().sum::<i32>(), total);
+            assert!(divisao.iter().all(|quantidade| *quantidade >= 0));
+        }
+    }
+
+    #[test]
+    fn arredondamento_para_cima_nao_passa_do_total() {
+        // 2/3 de 2 arredonda para 1 nas duas primeiras e a última fica sem formigas
+        let populacoes: Vec<Populacao> = [1.0, 1.0, 1.0].map(populacao).to_vec();
+        assert_eq!(dividir_formigas(&populacoes, 2), vec![1, 1, 0]);
+    }
+
+    #[test]
+    fn texto_do_manifesto_le_a_mesma_populacao() {
+        for texto in [
+            "1",
+            "2.5 alpha:4",
+            "1 alpha:4..8 velocidade:2 raio:0 modelo:sigmoide",
+        ] {
+            let populacao: Populacao = texto.parse().unwrap();
+            assert_eq!(populacao.to_string(), texto);
+            assert_eq!(
+                populacao.to_string().parse::<Populacao>().unwrap(),
+                populacao
+            );
+        }
+    }
+
+    #[test]
+    fn rejeita_valores_invalidos() {
+        for texto in [
+            "0",
+            "1 alpha:0",
+            "1 alpha:8..4",
+            "1 velocidade:0",
+            "1 velocidade:-1",
+            "1 raio:-2",
+            "1 modelo:desconhecido",
+            "1 cor:azul",
+        ] {
+            assert!(texto.parse::<Populacao>().is_err(), "{}", texto);
+        }
+
+        // As opções globais correspondentes seguem as mesmas regras
+        let mut configuracao = Configuracao::default();
+        for (chave, valor) in [
+            ("velocidade", "0"),
+            ("velocidade", "-1"),
+            ("raio_vizinhanca", "-1"),
+            ("raio_estagnacao", "-2"),
+        ] {
+            assert!(
+                configuracao.definir(chave, valor).is_err(),
+                "{} = {}",
+                chave,
+                valor
+            );
+        }
+        for (chave, valor) in [
+            ("velocidade", "1"),
+            ("raio_vizinhanca", "0"),
+            ("raio_estagnacao", "0"),
+        ] {
+            assert!(
+                configuracao.definir(chave, valor).is_ok(),
+                "{} = {}",
+                chave,
+                valor
+            );
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use super::classicos::KMedias;
+use super::densidade::distancia_entre_par_de_dados;
+use super::enxame::AlgoritmoEnxame;
+use super::grao::Grao;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Pós-processamento dos agrupamentos extraídos, feito sobre os centroides de cada um no
+/// espaço dos dados. Serve para juntar os agrupamentos partidos da grade das formigas e
+/// realocar os grãos largados no lugar errado.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Refinamento {
+    /// k-means que começa dos centroides dos agrupamentos: dos `grupos` maiores, ou de
+    /// todos os que têm mais de um grão quando `grupos` é `nenhum`
+    KMedias,
+    /// Une, sempre o par mais próximo primeiro, os agrupamentos cujos centroides estão a
+    /// menos do limiar um do outro
+    Unir(f64),
+}
+
+impl FromStr for Refinamento {
+    type Err = String;
+
+    fn from_str(valor: &str) -> Result<Self, Self::Err> {
+        match valor.split_once(char::is_whitespace) {
+            Some(("unir", limiar)) => limiar
+                .trim()
+                .parse()
+                .map(Refinamento::Unir)
+                .map_err(|e| format!("limiar inválido `{}`: {}", limiar.trim(), e)),
+            _ => match valor {
+                "kmedias" => Ok(Refinamento::KMedias),
+                _ => Err(format!("refinamento desconhecido `{}`", valor)),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Refinamento {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Refinamento::KMedias => write!(f, "kmedias"),
+            Refinamento::Unir(limiar) => write!(f, "unir {}", limiar),
+        }
+    }
+}
+
+/// Agrupamento com o seu centroide e quantos grãos tem
+struct Centroide {
+    rotulo: usize,
+    dados: Vec<f64>,
+    quantidade: usize,
+}
+
+/// Centroides dos agrupamentos, do maior para o menor
+fn centroides(graos: &[Grao], rotulos: &[usize]) -> Vec<Centroide> {
+    let mut por_rotulo: HashMap<usize, Centroide> = HashMap::new();
+    for (grao, &rotulo) in graos.iter().zip(rotulos) {
+        let centroide = por_rotulo.entry(rotulo).or_insert_with(|| Centroide {
+            rotulo,
+            dados: vec![0.0; grao.dados.len()],
+            quantidade: 0,
+        });
+        for (soma, valor) in centroide.dados.iter_mut().zip(&grao.dados) {
+            *soma += valor;
+        }
+        centroide.quantidade += 1;
+    }
+
+    let mut centroides: Vec<Centroide> = por_rotulo.into_values().collect();
+    for centroide in &mut centroides {
+        for valor in &mut centroide.dados {
+            *valor /= centroide.quantidade as f64;
+        }
+    }
+    centroides.sort_by(|a, b| {
+        b.quantidade
+            .cmp(&a.quantidade)
+            .then(a.rotulo.cmp(&b.rotulo))
+    });
+
+    centroides
+}
+
+/// Aplica o refinamento aos rótulos (um por grão, na mesma ordem) e retorna os novos
+pub fn refinar(
+    graos: &[Grao],
+    rotulos: &[usize],
+    refinamento: Refinamento,
+    grupos: Option<usize>,
+) -> Vec<usize> {
+    let centroides = centroides(graos, rotulos);
+
+    match refinamento {
+        Refinamento::KMedias => {
+            // Os centroides estão do maior agrupamento para o menor
+            let quantidade = grupos.unwrap_or_else(|| {
+                centroides
+                    .iter()
+                    .filter(|centroide| centroide.quantidade > 1)
+                    .count()
+            });
+            let sementes: Vec<Vec<f64>> = centroides
+                .into_iter()
+                .take(quantidade.max(1))
+                .map(|centroide| centroide.dados)
+                .collect();
+
+            let mut kmedias = KMedias::com_sementes(graos.to_vec(), sementes);
+            kmedias.inicializar();
+            while !kmedias.terminou() {
+                kmedias.passo();
+            }
+            kmedias.agrupamentos().1
+        }
+        Refinamento::Unir(limiar) => unir(rotulos, centroides, limiar),
+    }
+}
+
+/// Une o par de agrupamentos de centroides mais próximos enquanto a distância entre eles
+/// for menor que o limiar. O centroide da união é a média ponderada pelo tamanho.
+fn unir(rotulos: &[usize], mut centroides: Vec<Centroide>, limiar: f64) -> Vec<usize> {
+    // Rótulo original -> agrupamento em que ele terminou
+    let mut destino: HashMap<usize, usize> = centroides
+        .iter()
+        .map(|centroide| (centroide.rotulo, centroide.rotulo))
+        .collect();
+
+    loop {
+        let mut par: Option<(usize, usize, f64)> = None;
+        for i in 0..centroides.len() {
+            for j in i + 1..centroides.len() {
+                let distancia =
+                    distancia_entre_par_de_dados(&centroides[i].dados, &centroides[j].dados);
+                if distancia < limiar && par.is_none_or(|(_, _, menor)| distancia < menor) {
+                    par = Some((i, j, distancia));
+                }
+            }
+        }
+
+        let Some((i, j, _)) = par else { break };
+        let removido = centroides.swap_remove(j);
+        let mantido = &mut centroides[i];
+        let total = (mantido.quantidade + removido.quantidade) as f64;
+        for (valor, outro) in mantido.dados.iter_mut().zip(&removido.dados) {
+            *valor =
+                (*valor * mantido.quantidade as f64 + outro * removido.quantidade as f64) / total;
+        }
+        mantido.quantidade += removido.quantidade;
+
+        let rotulo_mantido = mantido.rotulo;
+        for alvo in destino.values_mut() {
+            if *alvo == removido.rotulo {
+                *alvo = rotulo_mantido;
+            }
+        }
+    }
+
+    rotulos.iter().map(|rotulo| destino[rotulo]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::outros::Ponto;
+
+    /// Um grão por valor, no eixo x
+    fn graos(valores: &[f64]) -> Vec<Grao> {
+        valores
+            .iter()
+            .map(|&x| Grao::new(Ponto { x: 0, y: 0 }, vec![x, 0.0], 0))
+            .collect()
+    }
+
+    #[test]
+    fn unir_junta_so_o_par_abaixo_do_limiar() {
+        let graos = graos(&[0.0, 1.0, 5.0]);
+        let rotulos = refinar(&graos, &[0, 1, 2], Refinamento::Unir(2.0), None);
+
+        assert_eq!(rotulos[0], rotulos[1]);
+        assert_ne!(rotulos[0], rotulos[2]);
+        assert_eq!(rotulos[2], 2);
+    }
+
+    #[test]
+    fn unir_remapeia_os_rotulos_em_cadeia() {
+        // A (0) e B (1) se unem primeiro; o centroide da união, em 0.4, fica a 1.5 de C
+        // (2), que então absorve os dois. D (3) fica longe de todos.
+        let graos = graos(&[0.0, 0.8, 1.9, 1.9, 10.0]);
+        let rotulos = refinar(&graos, &[0, 1, 2, 2, 3], Refinamento::Unir(1.6), None);
+
+        assert_eq!(rotulos, vec![2, 2, 2, 2, 3]);
+    }
+
+    #[test]
+    fn texto_do_refinamento_le_o_mesmo_valor() {
+        for refinamento in [Refinamento::KMedias, Refinamento::Unir(0.25)] {
+            assert_eq!(refinamento.to_string().parse(), Ok(refinamento));
+        }
+        assert!("unir x".parse::<Refinamento>().is_err());
+        assert!("outro".parse::<Refinamento>().is_err());
+    }
+}
@@ -0,0 +1,286 @@
+use super::grao::Grao;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use uuid::Uuid;
+
+/// Relação conhecida de antemão entre dois grãos
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Restricao {
+    /// Os dois grãos devem ficar no mesmo agrupamento (must-link)
+    Juntos,
+    /// Os dois grãos devem ficar em agrupamentos diferentes (cannot-link)
+    Separados,
+}
+
+/// Restrições entre pares de grãos, identificados pelo id
+#[derive(Clone, Debug, Default)]
+pub struct Restricoes {
+    pares: HashMap<(Uuid, Uuid), Restricao>,
+}
+
+/// Quantas restrições de cada tipo um agrupamento satisfaz
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Satisfacao {
+    pub juntos: usize,
+    pub total_juntos: usize,
+    pub separados: usize,
+    pub total_separados: usize,
+}
+
+impl fmt::Display for Satisfacao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} restrições satisfeitas ({}/{} juntos, {}/{} separados)",
+            self.juntos + self.separados,
+            self.total_juntos + self.total_separados,
+            self.juntos,
+            self.total_juntos,
+            self.separados,
+            self.total_separados
+        )
+    }
+}
+
+/// O par na ordem usada como chave, para que (a, b) e (b, a) sejam o mesmo
+fn par(a: &Uuid, b: &Uuid) -> (Uuid, Uuid) {
+    if a <= b {
+        (*a, *b)
+    } else {
+        (*b, *a)
+    }
+}
+
+impl Restricoes {
+    /// Lê as restrições de um arquivo com uma por linha, `juntos <i> <j>` ou
+    /// `separados <i> <j>`, em que `i` e `j` são as posições (a partir de 0) dos grãos no
+    /// arquivo de dados. Linhas vazias e iniciadas por `#` são ignoradas. Um par pode se
+    /// repetir, mas não com tipos diferentes.
+    pub fn ler_arquivo(caminho: &str, graos: &[Grao]) -> Result<Restricoes, Box<dyn Error>> {
+        let conteudo = fs::read_to_string(caminho)?;
+        let mut restricoes = Restricoes::default();
+        // Linha em que cada par apareceu primeiro, para as mensagens de erro
+        let mut linhas: HashMap<(Uuid, Uuid), usize> = HashMap::new();
+
+        for (numero, linha) in conteudo.lines().enumerate() {
+            let linha = linha.trim();
+            if linha.is_empty() || linha.starts_with('#') {
+                continue;
+            }
+
+            let partes: Vec<&str> = linha.split_whitespace().collect();
+            let (tipo, i, j) = match partes.as_slice() {
+                [tipo, i, j] => (*tipo, *i, *j),
+                _ => return Err(format!("linha {}: esperado `<tipo> <i> <j>`", numero + 1).into()),
+            };
+            let restricao = match tipo {
+                "juntos" => Restricao::Juntos,
+                "separados" => Restricao::Separados,
+                _ => {
+                    return Err(
+                        format!("linha {}: restrição desconhecida `{}`", numero + 1, tipo).into(),
+                    )
+                }
+            };
+
+            let mut ids = [i, j].into_iter().map(|indice| {
+                indice
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|indice| graos.get(indice))
+                    .map(|grao| grao.id)
+                    .ok_or_else(|| format!("linha {}: grão inválido `{}`", numero + 1, indice))
+            });
+            let (a, b) = (ids.next().unwrap()?, ids.next().unwrap()?);
+            let chave = par(&a, &b);
+            match linhas.get(&chave) {
+                Some(&anterior) if restricoes.pares[&chave] != restricao => {
+                    return Err(format!(
+                        "linha {}: contradiz a restrição da linha {} para o mesmo par",
+                        numero + 1,
+                        anterior
+                    )
+                    .into());
+                }
+                Some(_) => {}
+                None => {
+                    linhas.insert(chave, numero + 1);
+                    restricoes.pares.insert(chave, restricao);
+                }
+            }
+        }
+
+        Ok(restricoes)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pares.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pares.len()
+    }
+
+    /// Restrição entre os dois grãos, se houver
+    pub fn entre(&self, a: &Uuid, b: &Uuid) -> Option<Restricao> {
+        if self.pares.is_empty() {
+            return None;
+        }
+
+        self.pares.get(&par(a, b)).copied()
+    }
+
+    /// Conta as restrições satisfeitas pelos rótulos (um por grão, na mesma ordem). Os pares
+    /// com grãos que não aparecem entre os grãos dados não contam.
+    pub fn satisfeitas(&self, graos: &[Grao], rotulos: &[usize]) -> Satisfacao {
+        let rotulo_por_id: HashMap<Uuid, usize> = graos
+            .iter()
+            .zip(rotulos)
+            .map(|(grao, rotulo)| (grao.id, *rotulo))
+            .collect();
+
+        let mut satisfacao = Satisfacao {
+            juntos: 0,
+            total_juntos: 0,
+            separados: 0,
+            total_separados: 0,
+        };
+        for ((a, b), restricao) in &self.pares {
+            let (Some(rotulo_a), Some(rotulo_b)) = (rotulo_por_id.get(a), rotulo_por_id.get(b))
+            else {
+                continue;
+            };
+
+            match restricao {
+                Restricao::Juntos => {
+                    satisfacao.total_juntos += 1;
+                    if rotulo_a == rotulo_b {
+                        satisfacao.juntos += 1;
+                    }
+                }
+                Restricao::Separados => {
+                    satisfacao.total_separados += 1;
+                    if rotulo_a != rotulo_b {
+                        satisfacao.separados += 1;
+                    }
+                }
+            }
+        }
+
+        satisfacao
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::outros::Ponto;
+
+    fn graos(quantidade: usize) -> Vec<Grao> {
+        (0..quantidade)
+            .map(|_| Grao::new(Ponto { x: 0, y: 0 }, vec![0.0], 0))
+            .collect()
+    }
+
+    /// Escreve o conteúdo num arquivo temporário com o nome dado e lê as restrições dele
+    fn ler(nome: &str, conteudo: &str, graos: &[Grao]) -> Result<Restricoes, Box<dyn Error>> {
+        let caminho =
+            std::env::temp_dir().join(format!("restricoes_{}_{}.txt", std::process::id(), nome));
+        fs::write(&caminho, conteudo).unwrap();
+        let restricoes = Restricoes::ler_arquivo(caminho.to_str().unwrap(), graos);
+        fs::remove_file(&caminho).unwrap();
+        restricoes
+    }
+
+    #[test]
+    fn par_vale_nas_duas_ordens() {
+        let graos = graos(3);
+        let restricoes = ler(
+            "simetria",
+            "# comentário\n\njuntos 0 1\nseparados 2 1\n",
+            &graos,
+        )
+        .unwrap();
+
+        assert_eq!(restricoes.len(), 2);
+        for (a, b, restricao) in [(0, 1, Restricao::Juntos), (1, 2, Restricao::Separados)] {
+            assert_eq!(
+                restricoes.entre(&graos[a].id, &graos[b].id),
+                Some(restricao)
+            );
+            assert_eq!(
+                restricoes.entre(&graos[b].id, &graos[a].id),
+                Some(restricao)
+            );
+        }
+        assert_eq!(restricoes.entre(&graos[0].id, &graos[2].id), None);
+
+        // Repetir a mesma restrição não é contradição
+        let repetida = ler("repetida", "juntos 0 1\njuntos 1 0\n", &graos).unwrap();
+        assert_eq!(repetida.len(), 1);
+    }
+
+    #[test]
+    fn contradicao_indica_as_duas_linhas() {
+        let graos = graos(3);
+        let erro = ler(
+            "contradicao_linhas",
+            "juntos 0 1\n# comentário\nseparados 0 2\nseparados 1 0\n",
+            &graos,
+        )
+        .unwrap_err();
+        assert_eq!(
+            erro.to_string(),
+            "linha 4: contradiz a restrição da linha 1 para o mesmo par"
+        );
+    }
+
+    #[test]
+    fn rejeita_linhas_invalidas() {
+        let graos = graos(2);
+        for (nome, conteudo) in [
+            ("indice_fora", "juntos 0 2\n"),
+            ("indice_texto", "juntos 0 a\n"),
+            ("tipo", "perto 0 1\n"),
+            ("campos", "juntos 0\n"),
+            ("contradicao", "juntos 0 1\nseparados 1 0\n"),
+        ] {
+            assert!(ler(nome, conteudo, &graos).is_err(), "{}", conteudo);
+        }
+    }
+
+    #[test]
+    fn conta_as_restricoes_satisfeitas() {
+        let graos = graos(4);
+        let restricoes = ler(
+            "satisfeitas",
+            "juntos 0 1\njuntos 2 3\nseparados 0 2\nseparados 1 3\n",
+            &graos,
+        )
+        .unwrap();
+
+        // 0 e 1 juntos, 2 e 3 separados; 0 e 2 separados, 1 e 3 juntos
+        assert_eq!(
+            restricoes.satisfeitas(&graos, &[0, 0, 1, 0]),
+            Satisfacao {
+                juntos: 1,
+                total_juntos: 2,
+                separados: 1,
+                total_separados: 2,
+            }
+        );
+
+        // Pares com grãos ausentes não contam
+        assert_eq!(
+            restricoes.satisfeitas(&graos[..2], &[0, 0]),
+            Satisfacao {
+                juntos: 1,
+                total_juntos: 1,
+                separados: 0,
+                total_separados: 0,
+            }
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use super::configuracao::Configuracao;
+use super::constantes::PASSOS_POR_RESERVA;
+use super::formiga::Formiga;
+use super::mapa::Mapa;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+/// Lote de passos entregue a uma thread de trabalho junto com a sua fatia das formigas
+struct Tarefa {
+    indice: usize,
+    formigas: Vec<Formiga>,
+    contador: Arc<AtomicI64>,
+    passos: i64,
+    inicio: i64,
+}
+
+/// Fatia das formigas devolvida por uma thread, com os passos que ela executou
+struct Resultado {
+    indice: usize,
+    formigas: Vec<Formiga>,
+    passos: i64,
+}
+
+/// Conjunto fixo de threads de trabalho do modo assíncrono, criado uma vez e reaproveitado
+/// em todos os lotes. A cada lote as formigas são divididas em fatias e cada thread recebe
+/// uma pelo seu canal; ao fim do lote as fatias voltam e as formigas ficam de novo com o
+/// cenário, que precisa delas entre os lotes.
+#[derive(Debug)]
+pub struct Trabalhadores {
+    tarefas: Vec<Sender<Tarefa>>,
+    resultados: Receiver<Resultado>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl Trabalhadores {
+    /// Cria `num_threads` threads (pelo menos uma) que executam as formigas sobre o mapa
+    pub fn new(num_threads: usize, mapa: Arc<Mapa>, configuracao: Arc<Configuracao>) -> Self {
+        let (envio_resultados, resultados) = mpsc::channel();
+        let mut tarefas = vec![];
+        let mut threads = vec![];
+
+        for _ in 0..num_threads.max(1) {
+            let (envio, recebimento) = mpsc::channel::<Tarefa>();
+            let mapa = Arc::clone(&mapa);
+            let configuracao = Arc::clone(&configuracao);
+            let envio_resultados = envio_resultados.clone();
+
+            threads.push(thread::spawn(move || {
+                // Termina quando o cenário descarta o conjunto e o canal fecha
+                for mut tarefa in recebimento {
+                    // As formigas voltam ao cenário mesmo se a thread entrar em pânico
+                    let passos = panic::catch_unwind(AssertUnwindSafe(|| {
+                        executar_fatia(&mut tarefa, &mapa, &configuracao)
+                    }))
+                    .unwrap_or_else(|_| {
+                        eprintln!("Erro numa thread de trabalho");
+                        0
+                    });
+
+                    let resultado = Resultado {
+                        indice: tarefa.indice,
+                        formigas: tarefa.formigas,
+                        passos,
+                    };
+                    if envio_resultados.send(resultado).is_err() {
+                        return;
+                    }
+                }
+            }));
+            tarefas.push(envio);
+        }
+
+        Trabalhadores {
+            tarefas,
+            resultados,
+            threads,
+        }
+    }
+
+    /// Executa um lote de `passos` passos; `inicio` é o número de passos já executados
+    /// antes dele. Cada thread recebe uma fatia das formigas e as executa em rodízio,
+    /// reservando passos do contador do lote em blocos de `PASSOS_POR_RESERVA`.
+    /// Retorna a soma dos passos executados por cada thread.
+    pub fn executar_lote(&self, formigas: &mut Vec<Formiga>, passos: i64, inicio: i64) -> i64 {
+        if formigas.is_empty() {
+            return 0;
+        }
+
+        let contador = Arc::new(AtomicI64::new(passos));
+        let por_thread = formigas.len().div_ceil(self.tarefas.len());
+
+        // Divide as formigas em fatias, da última para a primeira
+        let mut fatias = vec![];
+        while !formigas.is_empty() {
+            let inicio_fatia = (formigas.len() - 1) / por_thread * por_thread;
+            fatias.push(formigas.split_off(inicio_fatia));
+        }
+        fatias.reverse();
+
+        let mut enviadas = 0;
+        for (indice, (fatia, envio)) in fatias.into_iter().zip(&self.tarefas).enumerate() {
+            let tarefa = Tarefa {
+                indice,
+                formigas: fatia,
+                contador: Arc::clone(&contador),
+                passos,
+                inicio,
+            };
+            match envio.send(tarefa) {
+                Ok(()) => enviadas += 1,
+                // A thread não existe mais: a fatia fica parada neste lote
+                Err(mpsc::SendError(tarefa)) => {
+                    eprintln!("Erro ao enviar um lote a uma thread de trabalho");
+                    formigas.extend(tarefa.formigas);
+                }
+            }
+        }
+
+        // Junta as fatias de volta na ordem original
+        let mut devolvidas: Vec<Resultado> = self.resultados.iter().take(enviadas).collect();
+        if devolvidas.len() < enviadas {
+            eprintln!("Erro ao aguardar uma thread de trabalho");
+        }
+        devolvidas.sort_by_key(|resultado| resultado.indice);
+
+        let paradas = std::mem::take(formigas);
+        let mut executados = 0;
+        for resultado in devolvidas {
+            formigas.extend(resultado.formigas);
+            executados += resultado.passos;
+        }
+        formigas.extend(paradas);
+
+        executados
+    }
+}
+
+impl Drop for Trabalhadores {
+    /// Fecha os canais e espera as threads terminarem
+    fn drop(&mut self) {
+        self.tarefas.clear();
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Executa as formigas da tarefa em rodízio até o contador do lote acabar
+fn executar_fatia(tarefa: &mut Tarefa, mapa: &Mapa, configuracao: &Configuracao) -> i64 {
+    let mut passos_executados: i64 = 0;
+    let mut proxima: usize = 0;
+
+    loop {
+        // Reserva um novo bloco de passos
+        let restante = tarefa
+            .contador
+            .fetch_sub(PASSOS_POR_RESERVA, Ordering::AcqRel);
+        if restante <= 0 {
+            return passos_executados;
+        }
+
+        // Passo da execução correspondente ao início do bloco reservado
+        let passo_atual = tarefa.inicio + tarefa.passos - restante;
+
+        for i in 0..restante.min(PASSOS_POR_RESERVA) {
+            tarefa.formigas[proxima].passo(mapa, configuracao, passo_atual + i);
+            proxima = (proxima + 1) % tarefa.formigas.len();
+            passos_executados += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulacao::formiga::Perfil;
+    use crate::simulacao::outros::Ponto;
+
+    #[test]
+    fn lotes_seguidos_devolvem_as_formigas_na_ordem() {
+        let configuracao = Arc::new(Configuracao::default());
+        let mapa = Arc::new(Mapa::new(configuracao.grade, vec![]));
+        let trabalhadores = Trabalhadores::new(3, mapa, Arc::clone(&configuracao));
+
+        let mut formigas: Vec<Formiga> = (0..7)
+            .map(|x| Formiga::new(Ponto { x, y: 0 }, 1.0, Perfil::default()))
+            .collect();
+        let ids: Vec<_> = formigas.iter().map(|f| f.id).collect();
+
+        for (passos, inicio) in [(1000, 0), (5, 1000), (0, 1005)] {
+            assert_eq!(
+                trabalhadores.executar_lote(&mut formigas, passos, inicio),
+                passos
+            );
+            assert_eq!(formigas.iter().map(|f| f.id).collect::<Vec<_>>(), ids);
+        }
+        assert_eq!(formigas.iter().map(|f| f.passos).sum::<u64>(), 1005);
+    }
+}